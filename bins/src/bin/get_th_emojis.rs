@@ -1,6 +1,6 @@
 use dotenv::dotenv;
+use serenity::http::Http;
 use serenity::model::id::GuildId;
-use serenity::prelude::*;
 use std::collections::HashMap;
 use std::env;
 use std::error::Error;
@@ -8,20 +8,19 @@ use std::fs::File;
 
 type Emojis = HashMap<String, u64>;
 
-struct Handler;
-impl EventHandler for Handler {}
-
-fn main() -> Result<(), Box<dyn Error>> {
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
     let id = 512404836306649098;
     let bot = env::var("DISCORD_TOKEN")?;
 
-    let client = Client::new(bot, Handler)?;
+    let http = Http::new_with_token(&bot);
     let guild = GuildId(id);
     dbg!("Logged in");
     let mut emojis: Emojis = HashMap::new();
     guild
-        .to_partial_guild(&client.cache_and_http.http)?
+        .to_partial_guild(&http)
+        .await?
         .emojis
         .values()
         .filter(|v| v.name.starts_with("th"))
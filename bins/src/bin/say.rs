@@ -1,10 +1,10 @@
 use serenity::{
+    async_trait,
     model::{gateway::Ready, id::ChannelId},
     prelude::*,
 };
 use std::env;
 use std::error::Error;
-use std::thread::sleep;
 use std::time::Duration;
 
 use dotenv::dotenv;
@@ -14,17 +14,18 @@ struct Handler {
     msg: String,
 }
 
+#[async_trait]
 impl EventHandler for Handler {
-    fn ready(&self, ctx: Context, ready: Ready) {
+    async fn ready(&self, ctx: Context, ready: Ready) {
         println!("Logged in as {}", ready.user.name);
 
         let channel_id = ChannelId(self.channel);
 
-        channel_id.broadcast_typing(&ctx.http).unwrap();
-        sleep(Duration::new(2, 500));
+        channel_id.broadcast_typing(&ctx.http).await.unwrap();
+        tokio::time::sleep(Duration::new(2, 500)).await;
 
         println!("Sending the message: {}", &self.msg);
-        if let Err(why) = channel_id.say(&ctx.http, self.msg.to_owned()) {
+        if let Err(why) = channel_id.say(&ctx.http, self.msg.to_owned()).await {
             eprintln!("Error while sending the message, error: {:#?}", why);
         }
 
@@ -34,7 +35,8 @@ impl EventHandler for Handler {
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
 
     let handler = {
@@ -57,9 +59,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     };
 
     let token = env::var("DISCORD_TOKEN")?;
-    let mut client = Client::new(&token, handler)?;
+    let mut client = Client::builder(&token).event_handler(handler).await?;
 
-    client.start()?;
+    client.start().await?;
 
     Ok(())
 }
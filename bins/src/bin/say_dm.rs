@@ -1,13 +1,11 @@
 use dotenv::dotenv;
+use serenity::http::Http;
 use serenity::model::id::UserId;
-use serenity::prelude::*;
 use std::env;
 use std::error::Error;
 
-struct Handler;
-impl EventHandler for Handler {}
-
-fn main() -> Result<(), Box<dyn Error>> {
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
 
     let token = env::var("DISCORD_TOKEN")?;
@@ -17,16 +15,15 @@ fn main() -> Result<(), Box<dyn Error>> {
     let id = env::var("ID").unwrap_or(args.remove(0)).parse::<u64>()?;
     let message = args.join(" ");
 
-    let client = Client::new(&token, Handler)?;
-    let http = &client.cache_and_http.http;
+    let http = Http::new_with_token(&token);
 
-    let user = http.get_current_user()?;
+    let user = http.get_current_user().await?;
     println!("Logged in as {}#{}", user.name, user.discriminator);
 
-    let dm = UserId(id).create_dm_channel(http)?;
+    let dm = UserId(id).create_dm_channel(&http).await?;
 
     println!("Sending the message: {}", message);
-    dm.say(http, message)?;
+    dm.say(&http, message).await?;
 
     println!("Done");
     Ok(())
@@ -0,0 +1,82 @@
+//! Export the database of the bot to a JSON archive, or restore it from one
+//! The bot must not be running, sled only allows a single process to open the database
+//!
+//! Usage:
+//!     tomodb export <database path> <output.json> [tree names...]
+//!     tomodb import <database path> <input.json> [--dry-run]
+
+use db::{Archive, DbInstance};
+use std::env;
+use std::error::Error;
+use std::fs;
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+const USAGE: &str = "Usage:
+    tomodb export <database path> <output.json> [tree names...]
+    tomodb import <database path> <input.json> [--dry-run]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.len() < 3 {
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    }
+
+    let res = match args[0].as_str() {
+        "export" => export(&args[1], &args[2], &args[3..]),
+        "import" => {
+            let dry_run = args[3..].iter().any(|v| v == "--dry-run" || v == "-n");
+            import(&args[1], &args[2], dry_run)
+        }
+
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(why) = res {
+        eprintln!("Error: {}", why);
+        std::process::exit(1);
+    }
+}
+
+fn export(db_path: &str, output: &str, trees: &[String]) -> Result<()> {
+    let db = DbInstance::new(db_path, None::<&[u8]>)?;
+    let archive = Archive::export(&db, &tomoka_rs::schemas(), |name| {
+        trees.is_empty() || trees.iter().any(|v| v == name)
+    })?;
+
+    fs::write(output, serde_json::to_vec_pretty(&archive)?)?;
+
+    println!(
+        "Exported {} records from {} trees to {}",
+        archive.len(),
+        archive.trees.len(),
+        output
+    );
+
+    if archive.skipped() > 0 {
+        eprintln!(
+            "Left out {} records which cannot be read with the current schemas",
+            archive.skipped()
+        );
+    }
+
+    Ok(())
+}
+
+fn import(db_path: &str, input: &str, dry_run: bool) -> Result<()> {
+    let archive: Archive = serde_json::from_slice(&fs::read(input)?)?;
+    let db = DbInstance::new(db_path, None::<&[u8]>)?;
+    let report = archive.import(&db, &tomoka_rs::schemas(), dry_run)?;
+
+    if !dry_run {
+        db.backend().flush()?;
+    }
+
+    println!("{}", report);
+    Ok(())
+}
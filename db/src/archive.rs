@@ -0,0 +1,429 @@
+use crate::index::{invalidate_indexes, is_index_tree};
+use crate::migration::{
+    Codec, MigrationReport, Schema, BASE_VERSION, META_TREE, QUARANTINE_SUFFIX,
+};
+use crate::{Bytes, DbInstance, Migrations, RawBatch, Result, TreeMeta};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bumped whenever the layout of the archive itself changes
+pub const ARCHIVE_FORMAT: u32 = 1;
+
+/// The name sled gives to the root tree
+const DEFAULT_TREE: &str = "__sled__default";
+
+/// How many keys of each kind of change are shown in the report
+const PREVIEW_KEYS: usize = 5;
+
+/// A portable JSON dump of the database
+/// Registered trees are written with their types, the others as raw hex
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Archive {
+    pub format: u32,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+    pub trees: BTreeMap<String, ArchivedTree>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchivedTree {
+    /// `None` for the trees without a registered schema
+    pub version: Option<u32>,
+    pub entries: Vec<ArchivedEntry>,
+    /// The records left out because they cannot be decoded with the schema
+    #[serde(default)]
+    pub skipped: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchivedEntry {
+    pub key: Value,
+    pub value: Value,
+}
+
+impl Archive {
+    /// Dump every tree accepted by the filter
+    /// A record which cannot be decoded is skipped with a warning instead of failing the export,
+    /// the number of them is kept in `ArchivedTree::skipped`
+    pub fn export<F>(db: &DbInstance, migrations: &Migrations, filter: F) -> Result<Self>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut trees = BTreeMap::new();
+
+        for name in db.backend().tree_names() {
+            let name = String::from_utf8_lossy(&name).into_owned();
+
+            // The indexes are derived data, they are built again after an import
+            if name == META_TREE || is_index_tree(&name) || !filter(&name) {
+                continue;
+            }
+
+            let fallback;
+            let (schema, version) = match migrations.get(&name) {
+                Some(s) => (s, Some(s.version())),
+                None => {
+                    fallback = Schema::raw(&name);
+                    (&fallback, None)
+                }
+            };
+
+            let mut entries = Vec::new();
+            let mut skipped = 0;
+
+            for entry in db.open(&name)?.tree().iter() {
+                let (key, value) = entry?;
+                let decoded = schema
+                    .key_codec()
+                    .decode(&key)
+                    .and_then(|k| Ok((k, schema.value_codec().decode(&value)?)));
+
+                match decoded {
+                    Ok((key, value)) => entries.push(ArchivedEntry { key, value }),
+                    Err(why) => {
+                        warn!(
+                            "Skipped the record {} of {} from the export | {}",
+                            raw_key(&key),
+                            display_name(&name),
+                            why
+                        );
+
+                        skipped += 1;
+                    }
+                }
+            }
+
+            trees.insert(
+                name,
+                ArchivedTree {
+                    version,
+                    entries,
+                    skipped,
+                },
+            );
+        }
+
+        Ok(Self {
+            format: ARCHIVE_FORMAT,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            trees,
+        })
+    }
+
+    /// Replace the content of every tree in the archive with the archived one
+    /// Trees which are not in the archive are left untouched
+    /// A tree archived at an older version is migrated in memory first, so it is compared
+    /// and written at the current version, and its quarantined records are kept
+    /// in the quarantine tree of `db`
+    /// Every tree is checked before anything is written
+    /// With `dry_run`, nothing is written and the report shows what would change
+    pub fn import(
+        &self,
+        db: &DbInstance,
+        migrations: &Migrations,
+        dry_run: bool,
+    ) -> Result<ImportReport> {
+        if self.format != ARCHIVE_FORMAT {
+            let err = format!("Unsupported archive format {}", self.format);
+            return Err(err.into());
+        }
+
+        let meta = db.open_tree::<String, TreeMeta, _>(META_TREE)?;
+        let mut report = ImportReport {
+            dry_run,
+            trees: Vec::new(),
+            migration: None,
+        };
+
+        let mut writes = Vec::with_capacity(self.trees.len());
+        let mut quarantines = Vec::new();
+
+        for (name, archived) in &self.trees {
+            let fallback = Schema::raw(name);
+            let (key_codec, value_codec, version) = match (migrations.get(name), archived.version) {
+                (Some(s), Some(v)) if v > s.version() => {
+                    let err = format!(
+                        "The tree {} in the archive is at version {}, which is newer than the supported version {}",
+                        name,
+                        v,
+                        s.version()
+                    );
+
+                    return Err(err.into());
+                }
+
+                (Some(s), Some(v)) => {
+                    let codec = s.value_codec_at(v).ok_or_else(|| {
+                        format!(
+                            "The tree {} in the archive is at version {}, which has no migration anymore",
+                            name, v
+                        )
+                    })?;
+
+                    (s.key_codec(), codec, Some(v))
+                }
+
+                // Archived before it had a schema, so as raw bytes of the first version
+                (Some(_), None) => (
+                    fallback.key_codec(),
+                    fallback.value_codec(),
+                    Some(BASE_VERSION),
+                ),
+
+                (None, v) => (fallback.key_codec(), fallback.value_codec(), v),
+            };
+
+            let mut records = Vec::with_capacity(archived.entries.len());
+            let mut json_keys = HashMap::with_capacity(archived.entries.len());
+
+            for entry in &archived.entries {
+                let key = key_codec.encode(&entry.key)?;
+                let value = value_codec.encode(&entry.value)?;
+                json_keys.insert(key.clone(), entry.key.to_string());
+                records.push((key, value));
+            }
+
+            let current = migrations.get(name).map(|s| s.version());
+            let version = match (version, current) {
+                (Some(from), Some(to)) if from < to => {
+                    let migrated = migrate_in_memory(name, from, records, migrations)?;
+
+                    records = migrated.records;
+                    quarantines.push((name, migrated.quarantined));
+                    report
+                        .migration
+                        .get_or_insert_with(Default::default)
+                        .trees
+                        .extend(migrated.report.trees);
+
+                    Some(to)
+                }
+
+                _ => version,
+            };
+
+            let mut desired = HashMap::with_capacity(records.len());
+
+            for (key, value) in records {
+                let json_key = json_keys
+                    .remove(&key)
+                    .unwrap_or_else(|| raw_key(&key).to_string());
+
+                desired.insert(key, (value, json_key));
+            }
+
+            let tree = db.open(name)?;
+            let mut batch = RawBatch::new();
+            let mut changes = TreeChanges::new(name);
+
+            for entry in tree.tree().iter() {
+                let (key, value) = entry?;
+
+                match desired.remove(&key) {
+                    Some((v, _)) if v == value => changes.unchanged += 1,
+                    Some((v, json_key)) => {
+                        changes.updated.push(json_key);
+                        batch.insert(key, v);
+                    }
+
+                    None => {
+                        let json_key = key_codec.decode(&key).unwrap_or_else(|_| raw_key(&key));
+                        changes.removed.push(json_key.to_string());
+                        batch.remove(key);
+                    }
+                }
+            }
+
+            for (key, (value, json_key)) in desired {
+                changes.added.push(json_key);
+                batch.insert(key, value);
+            }
+
+            writes.push((name, tree, batch, version));
+            report.trees.push(changes);
+        }
+
+        if dry_run {
+            return Ok(report);
+        }
+
+        // Quarantine first, as for a migration in place
+        for (name, records) in quarantines {
+            let mut batch = RawBatch::new();
+
+            for (key, value) in records {
+                batch.insert(key, value);
+            }
+
+            let quarantine = db.open(format!("{}{}", name, QUARANTINE_SUFFIX))?;
+            quarantine.tree().apply_batch(batch)?;
+        }
+
+        for (name, tree, batch, version) in writes {
+            tree.tree().apply_batch(batch)?;
+            invalidate_indexes(db, name)?;
+
+            if let Some(version) = version {
+                meta.insert(name, &TreeMeta { version })?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// The number of records in the archive
+    pub fn len(&self) -> usize {
+        self.trees.values().map(|v| v.entries.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of records left out of the archive
+    pub fn skipped(&self) -> usize {
+        self.trees.values().map(|v| v.skipped).sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub trees: Vec<TreeChanges>,
+    /// The migration of the trees archived at an older version
+    pub migration: Option<MigrationReport>,
+}
+
+/// The keys (in JSON) of the records changed in a tree
+#[derive(Debug)]
+pub struct TreeChanges {
+    pub tree: String,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl TreeChanges {
+    fn new(tree: &str) -> Self {
+        Self {
+            tree: display_name(tree).to_owned(),
+            added: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+            unchanged: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl ImportReport {
+    /// Whether the import changes (or would change) anything
+    pub fn has_changes(&self) -> bool {
+        self.trees.iter().any(|v| !v.is_empty())
+    }
+}
+
+impl fmt::Display for TreeChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {} added, {} updated, {} removed, {} unchanged",
+            self.tree,
+            self.added.len(),
+            self.updated.len(),
+            self.removed.len(),
+            self.unchanged
+        )?;
+
+        for (sign, keys) in &[("+", &self.added), ("~", &self.updated), ("-", &self.removed)] {
+            for key in keys.iter().take(PREVIEW_KEYS) {
+                writeln!(f, "  {} {}", sign, key)?;
+            }
+
+            if keys.len() > PREVIEW_KEYS {
+                writeln!(f, "  {} ...and {} more", sign, keys.len() - PREVIEW_KEYS)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.dry_run {
+            writeln!(f, "Dry run, nothing has been written")?;
+        }
+
+        if !self.has_changes() {
+            return write!(f, "Nothing to change");
+        }
+
+        for tree in self.trees.iter().filter(|v| !v.is_empty()) {
+            write!(f, "{}", tree)?;
+        }
+
+        if let Some(migration) = self.migration.as_ref().filter(|v| !v.is_empty()) {
+            write!(f, "Migrated the older trees\n{}", migration)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A key which the codec cannot read anymore, shown as its bytes
+fn raw_key(key: &[u8]) -> Value {
+    Codec::raw().decode(key).unwrap_or_default()
+}
+
+/// The records of a tree archived at an older version, once migrated
+struct Migrated {
+    records: Vec<(Bytes, Bytes)>,
+    quarantined: Vec<(Bytes, Bytes)>,
+    report: MigrationReport,
+}
+
+/// Run the migrations on the archived records of a tree, in a database of their own
+fn migrate_in_memory(
+    name: &str,
+    version: u32,
+    records: Vec<(Bytes, Bytes)>,
+    migrations: &Migrations,
+) -> Result<Migrated> {
+    let db = DbInstance::memory();
+    let tree = db.open(name)?;
+    let mut batch = RawBatch::new();
+
+    for (key, value) in records {
+        batch.insert(key, value);
+    }
+
+    tree.tree().apply_batch(batch)?;
+    db.open_tree::<String, TreeMeta, _>(META_TREE)?
+        .insert(&name.to_owned(), &TreeMeta { version })?;
+
+    let report = migrations.run(&db)?;
+    let quarantine = db.open(format!("{}{}", name, QUARANTINE_SUFFIX))?;
+
+    Ok(Migrated {
+        records: tree.tree().iter().collect::<Result<_>>()?,
+        quarantined: quarantine.tree().iter().collect::<Result<_>>()?,
+        report,
+    })
+}
+
+/// Show the root tree with a friendlier name
+fn display_name(tree: &str) -> &str {
+    match tree {
+        DEFAULT_TREE => "(root)",
+        _ => tree,
+    }
+}
@@ -0,0 +1,157 @@
+use crate::Result;
+use futures::channel::mpsc::UnboundedReceiver;
+use std::ops::Bound;
+use std::sync::Arc;
+
+/// A key or value as stored by a backend
+pub type Bytes = Vec<u8>;
+
+/// An iterator over the raw entries of a tree, ordered by the key bytes
+pub type RawIter = Box<dyn DoubleEndedIterator<Item = Result<(Bytes, Bytes)>> + Send>;
+
+/// A change made to a tree
+#[derive(Debug, Clone)]
+pub enum RawEvent {
+    Insert { key: Bytes, value: Bytes },
+    Remove { key: Bytes },
+}
+
+impl RawEvent {
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Self::Insert { key, .. } | Self::Remove { key } => key,
+        }
+    }
+}
+
+/// The changes of the keys starting with a prefix, it ends when the tree is dropped
+pub type RawWatcher = UnboundedReceiver<RawEvent>;
+
+/// The storage behind `DbInstance`
+/// Every tree of a backend is independent, and is created on its first opening
+pub trait Backend: Send + Sync {
+    fn open_tree(&self, name: &[u8]) -> Result<Arc<dyn BackendTree>>;
+
+    /// The tree used when `DbInstance` is opened without a name
+    fn default_tree(&self) -> Arc<dyn BackendTree>;
+
+    fn tree_names(&self) -> Vec<Bytes>;
+
+    /// Make sure everything written so far is persisted
+    fn flush(&self) -> Result<()>;
+
+    /// Run `f` on the named trees (in the same order) as a single transaction
+    /// Either every write of `f` is applied or none of them,
+    /// `f` may be run several times if the transaction conflicts with another write
+    fn transaction(
+        &self,
+        trees: &[Bytes],
+        f: &mut dyn FnMut(&[&dyn TxTree]) -> Result<()>,
+    ) -> Result<()>;
+}
+
+/// A tree inside of a transaction, the writes are only visible to the transaction until it commits
+pub trait TxTree {
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>>;
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()>;
+
+    fn remove(&self, key: &[u8]) -> Result<()>;
+}
+
+/// An ordered key-value tree
+pub trait BackendTree: Send + Sync {
+    fn name(&self) -> Bytes;
+
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>>;
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()>;
+
+    fn remove(&self, key: &[u8]) -> Result<()>;
+
+    /// Apply every operation of the batch atomically
+    fn apply_batch(&self, batch: RawBatch) -> Result<()>;
+
+    /// Swap the value of the key only if it is currently `old`
+    /// Return whether the swap happened
+    fn compare_and_swap(&self, key: &[u8], old: Option<&[u8]>, new: Option<&[u8]>)
+        -> Result<bool>;
+
+    fn range(&self, start: Bound<Bytes>, end: Bound<Bytes>) -> RawIter;
+
+    fn scan_prefix(&self, prefix: &[u8]) -> RawIter;
+
+    fn len(&self) -> usize;
+
+    fn clear(&self) -> Result<()>;
+
+    /// Subscribe to every later write on the keys starting with `prefix`
+    /// (an empty prefix for the whole tree), clearing the tree is not reported
+    fn watch_prefix(&self, prefix: &[u8]) -> Result<RawWatcher>;
+
+    fn iter(&self) -> RawIter {
+        self.range(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    fn first(&self) -> Result<Option<(Bytes, Bytes)>> {
+        self.iter().next().transpose()
+    }
+
+    fn last(&self) -> Result<Option<(Bytes, Bytes)>> {
+        self.iter().next_back().transpose()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A list of raw writes, applied in order
+#[derive(Debug, Default, Clone)]
+pub struct RawBatch(Vec<(Bytes, Option<Bytes>)>);
+
+impl RawBatch {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn insert<K: Into<Bytes>, V: Into<Bytes>>(&mut self, key: K, value: V) {
+        self.0.push((key.into(), Some(value.into())));
+    }
+
+    pub fn remove<K: Into<Bytes>>(&mut self, key: K) {
+        self.0.push((key.into(), None));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl IntoIterator for RawBatch {
+    type Item = (Bytes, Option<Bytes>);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// The smallest key which is bigger than every key starting with the prefix,
+/// `None` when there is no such key (the prefix is empty or only `0xff`)
+pub(crate) fn prefix_end(prefix: &[u8]) -> Option<Bytes> {
+    let mut end = prefix.to_vec();
+
+    while let Some(last) = end.pop() {
+        if last < u8::MAX {
+            end.push(last + 1);
+            return Some(end);
+        }
+    }
+
+    None
+}
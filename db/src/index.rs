@@ -0,0 +1,129 @@
+use crate::backend::Bytes;
+use crate::migration::{TreeMeta, META_TREE};
+use crate::{DbInstance, RawBatch, Result, ENCODER};
+use bincode::Options as _;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+/// An index of the tree `name` is stored in the tree `{name}{INDEX_SEPARATOR}{index}`
+pub const INDEX_SEPARATOR: &str = ".idx.";
+
+/// Stored in the meta tree once an index has been built
+const INDEX_VERSION: u32 = 1;
+
+type Extractor<K, V> = Box<dyn Fn(&K, &V) -> Result<Vec<Bytes>> + Send + Sync>;
+
+/// A secondary index, every entry is keyed by the serialized index key
+/// followed by the serialized key of the record, with an empty value
+pub(crate) struct Index<K, V> {
+    pub(crate) name: String,
+    pub(crate) tree: DbInstance,
+    keys: Extractor<K, V>,
+}
+
+impl<K, V> Index<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    pub(crate) fn new<IK, F>(name: &str, tree: DbInstance, f: F) -> Self
+    where
+        IK: Serialize,
+        F: Fn(&K, &V) -> Vec<IK> + Send + Sync + 'static,
+    {
+        let keys = move |key: &K, value: &V| -> Result<Vec<Bytes>> {
+            let mut res = Vec::new();
+
+            for ik in f(key, value) {
+                res.push(ENCODER.serialize(&ik)?);
+            }
+
+            Ok(res)
+        };
+
+        Self {
+            name: name.to_owned(),
+            tree,
+            keys: Box::new(keys),
+        }
+    }
+
+    /// The index entries of a record
+    pub(crate) fn entries(&self, raw_key: &[u8], key: &K, value: &V) -> Result<Vec<Bytes>> {
+        let mut entries = (self.keys)(key, value)?;
+
+        for entry in entries.iter_mut() {
+            entry.extend_from_slice(raw_key);
+        }
+
+        Ok(entries)
+    }
+
+    /// Same as `entries`, from the stored bytes
+    /// A record which cannot be read has no entry
+    pub(crate) fn raw_entries(&self, raw_key: &[u8], raw_value: &[u8]) -> Result<Vec<Bytes>> {
+        let data = (|| {
+            let k = ENCODER.deserialize(raw_key)?;
+            let v = ENCODER.deserialize(raw_value)?;
+            bincode::Result::<(K, V)>::Ok((k, v))
+        })();
+
+        match data {
+            Ok((k, v)) => self.entries(raw_key, &k, &v),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Build the index from scratch, only if it has never been built before
+    pub(crate) fn ensure_built(&self, source: &DbInstance) -> Result<()> {
+        let meta = source.open_tree::<String, TreeMeta, _>(META_TREE)?;
+        let name = String::from_utf8_lossy(&self.tree.tree().name()).into_owned();
+
+        if meta.get(&name)?.is_some() {
+            return Ok(());
+        }
+
+        self.tree.clear()?;
+        let mut batch = RawBatch::new();
+
+        for entry in source.tree().iter() {
+            let (key, value) = entry?;
+
+            for index_key in self.raw_entries(&key, &value)? {
+                batch.insert(index_key, Vec::new());
+            }
+        }
+
+        self.tree.tree().apply_batch(batch)?;
+        meta.insert(&name, &TreeMeta { version: INDEX_VERSION })
+    }
+}
+
+#[inline]
+pub fn index_tree_name(tree: &str, index: &str) -> String {
+    format!("{}{}{}", tree, INDEX_SEPARATOR, index)
+}
+
+#[inline]
+pub fn is_index_tree(name: &str) -> bool {
+    name.contains(INDEX_SEPARATOR)
+}
+
+/// Drop the indexes of a tree after it has been rewritten behind their back,
+/// they are built again on the next `DbTree::with_index`
+pub(crate) fn invalidate_indexes(db: &DbInstance, tree: &str) -> Result<()> {
+    let prefix = format!("{}{}", tree, INDEX_SEPARATOR);
+    let meta = db.open_tree::<String, TreeMeta, _>(META_TREE)?;
+
+    for name in db.backend().tree_names() {
+        if !name.starts_with(prefix.as_bytes()) {
+            continue;
+        }
+
+        let name = String::from_utf8_lossy(&name).into_owned();
+        db.open(&name)?.clear()?;
+        meta.remove(&name)?;
+    }
+
+    Ok(())
+}
@@ -3,13 +3,40 @@ use lazy_static::lazy_static;
 use log::error;
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
-use sled::{Db, Tree};
 use std::error::Error;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
+use tokio::task;
+
+mod archive;
+mod backend;
+mod index;
+mod memory;
+mod migration;
+mod scan;
+mod sled_backend;
+mod transaction;
+mod tree;
+mod ttl;
+mod watch;
+
+pub use archive::{Archive, ArchivedEntry, ArchivedTree, ImportReport, TreeChanges};
+pub use backend::{
+    Backend, BackendTree, Bytes, RawBatch, RawEvent, RawIter, RawWatcher, TxTree,
+};
+pub use index::{index_tree_name, is_index_tree, INDEX_SEPARATOR};
+pub use memory::{MemoryBackend, MemoryTree};
+pub use migration::{
+    Codec, MigrationReport, Migrations, Schema, TreeMeta, TreeReport, META_TREE,
+    QUARANTINE_SUFFIX,
+};
+pub use sled_backend::{SledBackend, SledTree};
+pub use transaction::{Transaction, TransactionTree};
+pub use tree::DbTree;
+pub use ttl::{Expiring, TTL_TREE};
+pub use watch::{Event, Watcher};
 
-type Manager = Arc<Db>;
 type Result<T> = std::result::Result<T, Box<dyn Error + Sync + Send>>;
 type Encoder = WithOtherEndian<DefaultOptions, BigEndian>;
 
@@ -20,18 +47,18 @@ lazy_static! {
 #[derive(Clone)]
 /// It's safe to clone because all the inners are already inside Arcs
 pub struct DbInstance {
-    tree: Option<Tree>,
-    manager: Manager,
+    tree: Arc<dyn BackendTree>,
+    backend: Arc<dyn Backend>,
 }
 
 impl Drop for DbInstance {
     fn drop(&mut self) {
-        self.manager.flush().ok();
+        self.backend.flush().ok();
     }
 }
 
 #[derive(Debug, Default)]
-pub struct Batch(sled::Batch);
+pub struct Batch(RawBatch);
 
 impl Batch {
     pub fn new() -> Self {
@@ -52,24 +79,24 @@ impl Batch {
     }
 }
 
-impl From<Batch> for sled::Batch {
-    fn from(b: Batch) -> sled::Batch {
+impl From<Batch> for RawBatch {
+    fn from(b: Batch) -> RawBatch {
         b.0
     }
 }
 
 pub struct Iter<K: DeserializeOwned, V: DeserializeOwned> {
-    iter: sled::Iter,
+    iter: RawIter,
     _marker: PhantomData<(K, V)>,
 }
 
 pub struct IterKey<K: DeserializeOwned> {
-    iter: sled::Iter,
+    iter: RawIter,
     _marker: PhantomData<K>,
 }
 
 impl<K: DeserializeOwned, V: DeserializeOwned> Iter<K, V> {
-    pub(crate) fn new(iter: sled::Iter) -> Self {
+    pub(crate) fn new(iter: RawIter) -> Self {
         Self {
             iter,
             _marker: PhantomData,
@@ -84,6 +111,36 @@ impl<K: DeserializeOwned, V: DeserializeOwned> Iter<K, V> {
     }
 }
 
+fn decode_entry<K, V>((key, val): (Bytes, Bytes)) -> Option<(K, V)>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    let data = (|| {
+        let k = ENCODER.deserialize(&key)?;
+        let v = ENCODER.deserialize(&val)?;
+        bincode::Result::<(K, V)>::Ok((k, v))
+    })();
+
+    match data {
+        Ok(e) => Some(e),
+        Err(why) => {
+            error!("Cannot deserialize data | {}", why);
+            None
+        }
+    }
+}
+
+fn decode_key<K: DeserializeOwned>((key, _): (Bytes, Bytes)) -> Option<K> {
+    match ENCODER.deserialize(&key) {
+        Ok(e) => Some(e),
+        Err(why) => {
+            error!("Cannot deserialize key | {}", why);
+            None
+        }
+    }
+}
+
 impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for Iter<K, V> {
     type Item = (K, V);
 
@@ -91,21 +148,17 @@ impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for Iter<K, V> {
         self.iter
             .by_ref()
             .filter_map(|v| v.ok())
-            .find_map(|(ref key, ref val)| {
-                let data = (|| {
-                    let k = ENCODER.deserialize(key)?;
-                    let v = ENCODER.deserialize(val)?;
-                    bincode::Result::<(K, V)>::Ok((k, v))
-                })();
-
-                match data {
-                    Ok(e) => Some(e),
-                    Err(why) => {
-                        error!("Cannot deserialize data | {}", why);
-                        None
-                    }
-                }
-            })
+            .find_map(decode_entry)
+    }
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> DoubleEndedIterator for Iter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .by_ref()
+            .rev()
+            .filter_map(|v| v.ok())
+            .find_map(decode_entry)
     }
 }
 
@@ -116,14 +169,17 @@ impl<K: DeserializeOwned> Iterator for IterKey<K> {
         self.iter
             .by_ref()
             .filter_map(|v| v.ok())
-            .map(|(key, _)| key)
-            .find_map(|ref key| match ENCODER.deserialize(key) {
-                Ok(e) => Some(e),
-                Err(why) => {
-                    error!("Cannot deserialize key | {}", why);
-                    None
-                }
-            })
+            .find_map(decode_key)
+    }
+}
+
+impl<K: DeserializeOwned> DoubleEndedIterator for IterKey<K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .by_ref()
+            .rev()
+            .filter_map(|v| v.ok())
+            .find_map(decode_key)
     }
 }
 
@@ -133,23 +189,39 @@ impl DbInstance {
         P: AsRef<Path>,
         N: Into<Option<&'a [u8]>>,
     {
-        let manager = get_db_manager(path)?;
-        let db = Self::from_manager(manager, name.into())?;
+        let backend = get_db_manager(path)?;
+        let db = Self::from_backend(backend, name.into())?;
         Ok(db)
     }
 
-    pub fn from_manager<N: AsRef<[u8]>>(manager: Manager, name: Option<N>) -> Result<Self> {
+    /// A fresh database living only in memory, for the tests
+    pub fn memory() -> Self {
+        let backend: Arc<dyn Backend> = Arc::new(MemoryBackend::new());
+        let tree = backend.default_tree();
+        Self { tree, backend }
+    }
+
+    pub fn from_backend<N: AsRef<[u8]>>(backend: Arc<dyn Backend>, name: Option<N>) -> Result<Self> {
         let tree = match name {
-            Some(n) => Some(manager.open_tree(n)?),
-            None => None,
+            Some(n) => backend.open_tree(n.as_ref())?,
+            None => backend.default_tree(),
         };
 
-        Ok(Self { tree, manager })
+        Ok(Self { tree, backend })
     }
 
     pub fn open<N: AsRef<[u8]>>(&self, tree: N) -> Result<Self> {
-        let manager = Arc::clone(&self.manager);
-        Self::from_manager(manager, Some(tree))
+        let backend = Arc::clone(&self.backend);
+        Self::from_backend(backend, Some(tree))
+    }
+
+    /// Open a tree with its key and value types fixed at the call site
+    /// so every later access goes through the same types
+    pub fn open_tree<K, V, N>(&self, tree: N) -> Result<DbTree<K, V>>
+    where
+        N: AsRef<[u8]>,
+    {
+        self.open(tree).map(DbTree::new)
     }
 
     pub fn get<K, V>(&self, key: &K) -> Result<Option<V>>
@@ -189,7 +261,7 @@ impl DbInstance {
     {
         let k = ENCODER.serialize(key)?;
         let v = ENCODER.serialize(value)?;
-        self.tree().insert(&k, v)?;
+        self.tree().insert(&k, &v)?;
         Ok(())
     }
 
@@ -200,7 +272,7 @@ impl DbInstance {
     }
 
     pub fn remove_many<K: Serialize, I: IntoIterator<Item = K>>(&self, keys: I) -> Result<()> {
-        let mut batch = sled::Batch::default();
+        let mut batch = RawBatch::new();
 
         for key in keys {
             let k = ENCODER.serialize(&key)?;
@@ -218,73 +290,117 @@ impl DbInstance {
 
     #[inline]
     pub fn clear(&self) -> Result<()> {
-        self.tree().clear().map_err(|e| Box::new(e) as Box<_>)
+        self.tree().clear()
     }
 
     #[inline]
-    pub fn tree(&self) -> &Tree {
-        match &self.tree {
-            Some(t) => t,
-            None => &**self.manager,
-        }
+    pub fn len(&self) -> usize {
+        self.tree().len()
     }
 
     #[inline]
-    pub fn manager(&self) -> Manager {
-        Arc::clone(&self.manager)
+    pub fn is_empty(&self) -> bool {
+        self.tree().is_empty()
+    }
+
+    #[inline]
+    pub fn tree(&self) -> &dyn BackendTree {
+        &*self.tree
+    }
+
+    #[inline]
+    pub fn backend(&self) -> Arc<dyn Backend> {
+        Arc::clone(&self.backend)
     }
 }
 
-// use tokio::runtime::Handle as TokioHandler;
-// impl DbInstance {
-//     #[inline]
-//     pub async fn get_async<K, V>(&self, key: &K) -> Result<Option<V>>
-//     where
-//         K: Serialize,
-//         V: DeserializeOwned,
-//     {
-//         TokioHandler::try_current()?.spawn_blocking(move || self.get(key))
-//     }
-//
-//     #[inline]
-//     pub async fn insert_async<K, V>(&self, key: &K, value: &V) -> Result<()>
-//     where
-//         K: Serialize,
-//         V: Serialize,
-//     {
-//         TokioHandler::try_current()?.spawn_blocking(move || self.insert(key, value))
-//     }
-//
-//     #[inline]
-//     pub async fn remove_async<K: Serialize>(&self, key: &K) -> Result<()> {
-//         TokioHandler::try_current()?.spawn_blocking(move || self.remove(key))
-//     }
-//
-//     #[inline]
-//     pub async fn remove_many_async<K: Serialize, I: IntoIterator<Item = K>>(&self, keys: I) -> Result<()> {
-//         TokioHandler::try_current()?.spawn_blocking(move || self.remove_many(keys))
-//     }
-//
-//     #[inline]
-//     pub async fn batch_async(&self, batch: Batch) -> Result<()> {
-//         TokioHandler::try_current()?.spawn_blocking(move || self.batch(batch))
-//     }
-//
-//     #[inline]
-//     pub async fn clear_async(&self) -> Result<()> {
-//         TokioHandler::try_current()?.spawn_blocking(move || self.clear())
-//     }
-//
-// }
+impl DbInstance {
+    /// Run a closure on the underlying tree in the blocking thread pool
+    /// Only the (cheap to clone) tree handle is moved into the closure,
+    /// so keys and values have to be serialized before calling this
+    async fn blocking<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(Arc<dyn BackendTree>) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let tree = Arc::clone(&self.tree);
+        task::spawn_blocking(move || f(tree)).await?
+    }
+
+    pub async fn get_async<K, V>(&self, key: &K) -> Result<Option<V>>
+    where
+        K: Serialize,
+        V: DeserializeOwned,
+    {
+        let k = ENCODER.serialize(key)?;
+        let res = self
+            .blocking(move |tree| tree.get(&k))
+            .await?
+            .and_then(|ref v| ENCODER.deserialize(v).ok());
+
+        Ok(res)
+    }
+
+    /// Collect every entry of the tree, the iteration is done in the blocking thread pool
+    pub async fn get_all_async<K, V>(&self) -> Result<Vec<(K, V)>>
+    where
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        self.blocking(|tree| Ok(Iter::<K, V>::new(tree.iter()).collect()))
+            .await
+    }
+
+    pub async fn insert_async<K, V>(&self, key: &K, value: &V) -> Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let k = ENCODER.serialize(key)?;
+        let v = ENCODER.serialize(value)?;
+
+        self.blocking(move |tree| tree.insert(&k, &v))
+        .await
+    }
+
+    pub async fn remove_async<K: Serialize>(&self, key: &K) -> Result<()> {
+        let k = ENCODER.serialize(key)?;
+
+        self.blocking(move |tree| tree.remove(&k))
+        .await
+    }
+
+    pub async fn remove_many_async<K, I>(&self, keys: I) -> Result<()>
+    where
+        K: Serialize,
+        I: IntoIterator<Item = K>,
+    {
+        let mut batch = RawBatch::new();
+
+        for key in keys {
+            let k = ENCODER.serialize(&key)?;
+            batch.remove(k);
+        }
+
+        self.blocking(move |tree| tree.apply_batch(batch))
+        .await
+    }
+
+    pub async fn batch_async(&self, batch: Batch) -> Result<()> {
+        self.blocking(move |tree| tree.apply_batch(batch.into()))
+        .await
+    }
+
+    pub async fn clear_async(&self) -> Result<()> {
+        self.blocking(|tree| tree.clear())
+        .await
+    }
+}
 
 #[inline]
-pub fn get_db_manager(path: impl AsRef<Path>) -> Result<Manager> {
-    sled::Config::new()
-        .path(path)
-        .use_compression(true)
-        .open()
-        .map(Arc::new)
-        .map_err(|v| Box::new(v) as Box<_>)
+pub fn get_db_manager(path: impl AsRef<Path>) -> Result<Arc<dyn Backend>> {
+    let backend = SledBackend::open(path)?;
+    Ok(Arc::new(backend))
 }
 
 /// Retry until we able to get to database
@@ -312,7 +428,7 @@ where
                 }
 
                 let wait = core::time::Duration::from_millis(500);
-                tokio::time::delay_for(wait).await;
+                tokio::time::sleep(wait).await;
             }
         }
     }
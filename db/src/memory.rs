@@ -0,0 +1,294 @@
+use crate::backend::{
+    prefix_end, Backend, BackendTree, Bytes, RawBatch, RawEvent, RawIter, RawWatcher, TxTree,
+};
+use crate::Result;
+use futures::channel::mpsc::{self, UnboundedSender};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::sync::{Arc, Mutex, RwLock, RwLockWriteGuard};
+
+/// The name sled gives to its root tree, kept the same here
+const DEFAULT_TREE: &[u8] = b"__sled__default";
+
+/// A backend living only in memory, nothing is persisted
+/// Made for the tests, every instance is a fresh and empty database
+#[derive(Default)]
+pub struct MemoryBackend {
+    trees: Mutex<BTreeMap<Bytes, Arc<MemoryTree>>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn tree(&self, name: &[u8]) -> Arc<MemoryTree> {
+        let mut trees = self.trees.lock().unwrap();
+
+        let tree = trees.entry(name.to_vec()).or_insert_with(|| {
+            Arc::new(MemoryTree {
+                name: name.to_vec(),
+                data: Default::default(),
+                subscribers: Default::default(),
+            })
+        });
+
+        Arc::clone(tree)
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn open_tree(&self, name: &[u8]) -> Result<Arc<dyn BackendTree>> {
+        let tree: Arc<dyn BackendTree> = self.tree(name);
+        Ok(tree)
+    }
+
+    fn default_tree(&self) -> Arc<dyn BackendTree> {
+        self.tree(DEFAULT_TREE)
+    }
+
+    fn tree_names(&self) -> Vec<Bytes> {
+        self.trees.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Every tree of the transaction is locked for its whole duration,
+    /// so it can never conflict and `f` is run only once
+    fn transaction(
+        &self,
+        trees: &[Bytes],
+        f: &mut dyn FnMut(&[&dyn TxTree]) -> Result<()>,
+    ) -> Result<()> {
+        let opened: Vec<_> = trees.iter().map(|name| self.tree(name)).collect();
+
+        // Lock in the name order (and only once per tree) to not deadlock with another transaction
+        let mut order: Vec<usize> = (0..opened.len()).collect();
+        order.sort_by(|&a, &b| trees[a].cmp(&trees[b]));
+        order.dedup_by(|a, b| trees[*a] == trees[*b]);
+
+        let mut guards: BTreeMap<&[u8], RwLockWriteGuard<'_, _>> = BTreeMap::new();
+        for i in order {
+            guards.insert(trees[i].as_slice(), opened[i].data.write().unwrap());
+        }
+
+        let pending: BTreeMap<&[u8], RefCell<Pending>> = guards
+            .keys()
+            .map(|&name| (name, RefCell::new(Pending::new())))
+            .collect();
+
+        let views: Vec<_> = trees
+            .iter()
+            .map(|name| MemoryTxTree {
+                data: &guards[name.as_slice()],
+                pending: &pending[name.as_slice()],
+            })
+            .collect();
+
+        let views_ref: Vec<&dyn TxTree> = views.iter().map(|v| v as &dyn TxTree).collect();
+        f(&views_ref)?;
+
+        drop(views_ref);
+        drop(views);
+
+        for (name, writes) in pending {
+            let data = guards.get_mut(name).unwrap();
+            let tree = &opened[trees.iter().position(|v| v == name).unwrap()];
+
+            for (key, value) in writes.into_inner() {
+                tree.notify(&key, value.as_deref());
+
+                match value {
+                    Some(v) => data.insert(key, v),
+                    None => data.remove(&key),
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The writes of a transaction, `None` for a removal
+type Pending = BTreeMap<Bytes, Option<Bytes>>;
+
+struct MemoryTxTree<'a> {
+    data: &'a BTreeMap<Bytes, Bytes>,
+    pending: &'a RefCell<Pending>,
+}
+
+impl TxTree for MemoryTxTree<'_> {
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        match self.pending.borrow().get(key) {
+            Some(v) => Ok(v.clone()),
+            None => Ok(self.data.get(key).cloned()),
+        }
+    }
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.pending
+            .borrow_mut()
+            .insert(key.to_vec(), Some(value.to_vec()));
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<()> {
+        self.pending.borrow_mut().insert(key.to_vec(), None);
+        Ok(())
+    }
+}
+
+pub struct MemoryTree {
+    name: Bytes,
+    data: RwLock<BTreeMap<Bytes, Bytes>>,
+    subscribers: Mutex<Vec<(Bytes, UnboundedSender<RawEvent>)>>,
+}
+
+impl MemoryTree {
+    /// Send a write to the subscribers watching its key, `None` for a removal
+    fn notify(&self, key: &[u8], value: Option<&[u8]>) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+
+        subscribers.retain(|(prefix, sender)| {
+            if !key.starts_with(prefix) {
+                return !sender.is_closed();
+            }
+
+            let event = match value {
+                Some(v) => RawEvent::Insert {
+                    key: key.to_vec(),
+                    value: v.to_vec(),
+                },
+
+                None => RawEvent::Remove { key: key.to_vec() },
+            };
+
+            sender.unbounded_send(event).is_ok()
+        });
+    }
+
+    /// The iterators work on a snapshot, later writes are not seen by them
+    /// A range ending before it starts is empty, as with sled, instead of a panic
+    fn snapshot(&self, start: Bound<Bytes>, end: Bound<Bytes>) -> RawIter {
+        let is_empty = match (&start, &end) {
+            (Bound::Included(s), Bound::Included(e)) => s > e,
+            (Bound::Included(s), Bound::Excluded(e))
+            | (Bound::Excluded(s), Bound::Included(e))
+            | (Bound::Excluded(s), Bound::Excluded(e)) => s >= e,
+            _ => false,
+        };
+
+        if is_empty {
+            return Box::new(std::iter::empty());
+        }
+
+        let entries: Vec<_> = self
+            .data
+            .read()
+            .unwrap()
+            .range((start, end))
+            .map(|(k, v)| Ok((k.to_owned(), v.to_owned())))
+            .collect();
+
+        Box::new(entries.into_iter())
+    }
+}
+
+impl BackendTree for MemoryTree {
+    fn name(&self) -> Bytes {
+        self.name.to_owned()
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        Ok(self.data.read().unwrap().get(key).cloned())
+    }
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.data
+            .write()
+            .unwrap()
+            .insert(key.to_vec(), value.to_vec());
+
+        self.notify(key, Some(value));
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<()> {
+        self.data.write().unwrap().remove(key);
+        self.notify(key, None);
+        Ok(())
+    }
+
+    fn apply_batch(&self, batch: RawBatch) -> Result<()> {
+        let mut data = self.data.write().unwrap();
+
+        for (key, value) in batch {
+            self.notify(&key, value.as_deref());
+
+            match value {
+                Some(v) => data.insert(key, v),
+                None => data.remove(&key),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn compare_and_swap(
+        &self,
+        key: &[u8],
+        old: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> Result<bool> {
+        let mut data = self.data.write().unwrap();
+
+        if data.get(key).map(Vec::as_slice) != old {
+            return Ok(false);
+        }
+
+        match new {
+            Some(v) => data.insert(key.to_vec(), v.to_vec()),
+            None => data.remove(key),
+        };
+
+        self.notify(key, new);
+        Ok(true)
+    }
+
+    fn range(&self, start: Bound<Bytes>, end: Bound<Bytes>) -> RawIter {
+        self.snapshot(start, end)
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> RawIter {
+        let end = match prefix_end(prefix) {
+            Some(v) => Bound::Excluded(v),
+            None => Bound::Unbounded,
+        };
+
+        self.snapshot(Bound::Included(prefix.to_vec()), end)
+    }
+
+    fn len(&self) -> usize {
+        self.data.read().unwrap().len()
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.data.write().unwrap().clear();
+        Ok(())
+    }
+
+    fn watch_prefix(&self, prefix: &[u8]) -> Result<RawWatcher> {
+        let (sender, receiver) = mpsc::unbounded();
+
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push((prefix.to_vec(), sender));
+
+        Ok(receiver)
+    }
+}
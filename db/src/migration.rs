@@ -0,0 +1,429 @@
+use crate::index::invalidate_indexes;
+use crate::{DbInstance, RawBatch, Result, ENCODER};
+use bincode::Options as _;
+use log::{error, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The tree holding the schema version of every registered tree
+pub const META_TREE: &str = "__meta";
+
+/// Records which cannot be migrated are moved into `{tree}{QUARANTINE_SUFFIX}`
+/// with their original key and bytes, so nothing is lost
+pub const QUARANTINE_SUFFIX: &str = ".quarantine";
+
+/// The version assumed for the trees written before the versioning existed
+pub const BASE_VERSION: u32 = 1;
+
+/// `None` drops the record
+type Step = Box<dyn Fn(&[u8]) -> Result<Option<Vec<u8>>> + Send + Sync>;
+type Decoder = Box<dyn Fn(&[u8]) -> Result<Value> + Send + Sync>;
+type Encoder = Box<dyn Fn(&Value) -> Result<Vec<u8>> + Send + Sync>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeMeta {
+    pub version: u32,
+}
+
+/// Convert the stored bytes from and to JSON
+/// A typed codec also tells whether a record matches the current layout
+pub struct Codec {
+    decode: Decoder,
+    encode: Encoder,
+}
+
+impl Codec {
+    pub fn typed<T>() -> Self
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Self {
+            decode: Box::new(|bytes: &[u8]| -> Result<Value> {
+                let data: T = ENCODER.deserialize(bytes)?;
+                Ok(serde_json::to_value(data)?)
+            }),
+            encode: Box::new(|value: &Value| -> Result<Vec<u8>> {
+                let data = T::deserialize(value)?;
+                Ok(ENCODER.serialize(&data)?)
+            }),
+        }
+    }
+
+    /// For the data without a fixed type, the bytes are kept as-is in hex
+    pub fn raw() -> Self {
+        Self {
+            decode: Box::new(|bytes: &[u8]| Ok(raw_to_json(bytes))),
+            encode: Box::new(raw_from_json),
+        }
+    }
+
+    #[inline]
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value> {
+        (self.decode)(bytes)
+    }
+
+    #[inline]
+    pub fn encode(&self, value: &Value) -> Result<Vec<u8>> {
+        (self.encode)(value)
+    }
+}
+
+/// The current layout of a tree, with the steps to upgrade the older ones
+pub struct Schema {
+    tree: String,
+    version: u32,
+    steps: BTreeMap<u32, Step>,
+    key: Codec,
+    value: Codec,
+    /// The value codecs of the older versions, known from their migration step
+    old_values: BTreeMap<u32, Codec>,
+}
+
+impl Schema {
+    /// A tree where every record is a `K` -> `V`
+    pub fn new<K, V>(tree: &str, version: u32) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        Self::with_codecs(tree, version, Codec::typed::<K>(), Codec::typed::<V>())
+    }
+
+    /// A tree holding different types of value under the same key type,
+    /// only the keys can be checked for these
+    pub fn keyed<K>(tree: &str, version: u32) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+    {
+        Self::with_codecs(tree, version, Codec::typed::<K>(), Codec::raw())
+    }
+
+    /// A tree without any known type, used for the trees nobody registered
+    pub fn raw(tree: &str) -> Self {
+        Self::with_codecs(tree, BASE_VERSION, Codec::raw(), Codec::raw())
+    }
+
+    pub fn with_codecs(tree: &str, version: u32, key: Codec, value: Codec) -> Self {
+        Self {
+            tree: tree.to_owned(),
+            version,
+            steps: BTreeMap::new(),
+            key,
+            value,
+            old_values: BTreeMap::new(),
+        }
+    }
+
+    /// Register the upgrade of the values from version `from` to `from + 1`
+    pub fn migrate<Old, New, F>(mut self, from: u32, f: F) -> Self
+    where
+        Old: Serialize + DeserializeOwned,
+        New: Serialize,
+        F: Fn(Old) -> New + Send + Sync + 'static,
+    {
+        let step = move |bytes: &[u8]| -> Result<Option<Vec<u8>>> {
+            let old: Old = ENCODER.deserialize(bytes)?;
+            let new = ENCODER.serialize(&f(old))?;
+            Ok(Some(new))
+        };
+
+        self.steps.insert(from, Box::new(step));
+        self.old_values.insert(from, Codec::typed::<Old>());
+        self
+    }
+
+    /// Same as `migrate`, but working on the raw bytes,
+    /// for the trees without a single value type
+    pub fn migrate_raw<F>(mut self, from: u32, f: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<Vec<u8>> + Send + Sync + 'static,
+    {
+        let step = move |bytes: &[u8]| f(bytes).map(Some);
+
+        self.steps.insert(from, Box::new(step));
+        self.old_values.insert(from, Codec::raw());
+        self
+    }
+
+    /// Remove the records of version `from` instead of upgrading them,
+    /// for the caches which are cheaper to fetch again than to migrate
+    pub fn discard(mut self, from: u32) -> Self {
+        self.steps.insert(from, Box::new(|_: &[u8]| Ok(None)));
+        self.old_values.insert(from, Codec::raw());
+        self
+    }
+
+    #[inline]
+    pub fn tree(&self) -> &str {
+        &self.tree
+    }
+
+    #[inline]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    #[inline]
+    pub fn key_codec(&self) -> &Codec {
+        &self.key
+    }
+
+    #[inline]
+    pub fn value_codec(&self) -> &Codec {
+        &self.value
+    }
+
+    /// The codec of the values at an older version, as long as it has a migration step
+    /// The keys never change between versions
+    pub fn value_codec_at(&self, version: u32) -> Option<&Codec> {
+        if version == self.version {
+            Some(&self.value)
+        } else {
+            self.old_values.get(&version)
+        }
+    }
+
+    /// Whether a record can be read with the current layout
+    pub fn validate(&self, key: &[u8], value: &[u8]) -> bool {
+        self.key.decode(key).is_ok() && self.value.decode(value).is_ok()
+    }
+
+    /// Run all the steps needed to bring a value from version `from` to the current one
+    /// `None` when a step discards it
+    fn upgrade(&self, from: u32, value: &[u8]) -> Result<Option<Vec<u8>>> {
+        let mut value = value.to_vec();
+
+        for version in from..self.version {
+            let step = self
+                .steps
+                .get(&version)
+                .ok_or_else(|| format!("No migration from version {} of {}", version, self.tree))?;
+
+            value = match step(&value)? {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+        }
+
+        Ok(Some(value))
+    }
+}
+
+/// A registry of the schemas, run it once after opening the database
+#[derive(Default)]
+pub struct Migrations {
+    schemas: Vec<Schema>,
+}
+
+impl Migrations {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn register(mut self, schema: Schema) -> Self {
+        self.schemas.push(schema);
+        self
+    }
+
+    pub fn schemas(&self) -> &[Schema] {
+        &self.schemas
+    }
+
+    pub fn get(&self, tree: &str) -> Option<&Schema> {
+        self.schemas.iter().find(|v| v.tree == tree)
+    }
+
+    /// Bring every registered tree to its current version
+    /// Trees that are already up to date are skipped without reading them
+    pub fn run(&self, db: &DbInstance) -> Result<MigrationReport> {
+        let meta = db.open_tree::<String, TreeMeta, _>(META_TREE)?;
+        let mut report = MigrationReport::default();
+
+        for schema in &self.schemas {
+            let stored = meta.get(&schema.tree)?.map(|v| v.version);
+
+            if let Some(version) = stored.filter(|&v| v > schema.version) {
+                let err = format!(
+                    "The tree {} is at version {}, which is newer than the supported version {}",
+                    schema.tree, version, schema.version
+                );
+
+                return Err(err.into());
+            }
+
+            if stored == Some(schema.version) {
+                continue;
+            }
+
+            let tree = db.open(&schema.tree)?;
+
+            if tree.is_empty() {
+                meta.insert(&schema.tree, &TreeMeta { version: schema.version })?;
+                continue;
+            }
+
+            let res = migrate_tree(db, &tree, schema, stored)?;
+            invalidate_indexes(db, &schema.tree)?;
+            meta.insert(&schema.tree, &TreeMeta { version: schema.version })?;
+            report.trees.push(res);
+        }
+
+        Ok(report)
+    }
+}
+
+fn migrate_tree(
+    db: &DbInstance,
+    tree: &DbInstance,
+    schema: &Schema,
+    stored: Option<u32>,
+) -> Result<TreeReport> {
+    let from = stored.unwrap_or(BASE_VERSION);
+    let quarantine = db.open(format!("{}{}", schema.tree, QUARANTINE_SUFFIX))?;
+
+    let mut report = TreeReport {
+        tree: schema.tree.to_owned(),
+        from: stored,
+        to: schema.version,
+        migrated: 0,
+        discarded: 0,
+        quarantined: 0,
+    };
+
+    let mut batch = RawBatch::new();
+    let mut quarantined = RawBatch::new();
+
+    for entry in tree.tree().iter() {
+        let (key, value) = entry?;
+
+        let upgraded = schema
+            .upgrade(from, &value)
+            .and_then(|v| match v {
+                Some(v) if !schema.validate(&key, &v) => {
+                    Err("The migrated record does not match the current schema".into())
+                }
+                v => Ok(v),
+            });
+
+        match upgraded {
+            Ok(Some(v)) => {
+                if v != value {
+                    batch.insert(key, v);
+                    report.migrated += 1;
+                }
+            }
+
+            Ok(None) => {
+                batch.remove(key);
+                report.discarded += 1;
+            }
+
+            Err(why) => {
+                error!("Cannot migrate a record of {} | {}", schema.tree, why);
+                batch.remove(key.clone());
+                quarantined.insert(key, value);
+                report.quarantined += 1;
+            }
+        }
+    }
+
+    // Quarantine first, a crash in between leaves a copy instead of a loss
+    quarantine.tree().apply_batch(quarantined)?;
+    tree.tree().apply_batch(batch)?;
+
+    if report.quarantined > 0 {
+        warn!(
+            "Quarantined {} records of {} into {}{}",
+            report.quarantined, schema.tree, schema.tree, QUARANTINE_SUFFIX
+        );
+    }
+
+    Ok(report)
+}
+
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub trees: Vec<TreeReport>,
+}
+
+#[derive(Debug)]
+pub struct TreeReport {
+    pub tree: String,
+    /// `None` when the tree was written before the versioning existed
+    pub from: Option<u32>,
+    pub to: u32,
+    pub migrated: usize,
+    /// Removed by a `discard` step
+    pub discarded: usize,
+    pub quarantined: usize,
+}
+
+impl MigrationReport {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    pub fn quarantined(&self) -> usize {
+        self.trees.iter().map(|v| v.quarantined).sum()
+    }
+}
+
+impl fmt::Display for TreeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.from {
+            Some(v) => write!(f, "{} v{} -> v{}", self.tree, v, self.to)?,
+            None => write!(f, "{} (unversioned) -> v{}", self.tree, self.to)?,
+        }
+
+        write!(
+            f,
+            ": {} migrated, {} discarded, {} quarantined",
+            self.migrated, self.discarded, self.quarantined
+        )
+    }
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.trees.is_empty() {
+            return write!(f, "Every tree is up to date");
+        }
+
+        for tree in &self.trees {
+            writeln!(f, "{}", tree)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The JSON key used to mark a raw (hex encoded) data
+const RAW_KEY: &str = "$bytes";
+
+fn raw_to_json(bytes: &[u8]) -> Value {
+    let hex: String = bytes.iter().map(|v| format!("{:02x}", v)).collect();
+    let mut map = Map::new();
+
+    map.insert(RAW_KEY.to_owned(), Value::String(hex));
+    Value::Object(map)
+}
+
+fn raw_from_json(value: &Value) -> Result<Vec<u8>> {
+    let hex = value
+        .get(RAW_KEY)
+        .and_then(Value::as_str)
+        .ok_or("Expected a raw data")?;
+
+    if !hex.is_ascii() || hex.len() % 2 != 0 {
+        return Err("Invalid raw data length".into());
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| Box::new(e) as Box<_>))
+        .collect()
+}
@@ -0,0 +1,169 @@
+//! Ordered lookups on the serialized keys
+//!
+//! The backends order the keys by their bytes, so the order of the typed keys is the
+//! order of their bincode (big endian, varint) encoding, which is:
+//! - unsigned integers, `char` and `bool` sort by value
+//! - signed integers are zigzag encoded, they only sort by value when non-negative
+//! - strings and byte sequences sort by length first, then by content
+//! - tuples and structs sort field by field, enums by variant index first
+//!
+//! Use an unsigned (or non-negative) integer as the leading key field
+//! whenever the range or order of the keys matters
+
+use crate::{decode_entry, DbInstance, DbTree, Iter, Result, ENCODER};
+use bincode::Options as _;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use std::ops::{Bound, RangeBounds};
+
+fn encode_bound<K: Serialize>(bound: Bound<&K>) -> Result<Bound<Vec<u8>>> {
+    let res = match bound {
+        Bound::Included(k) => Bound::Included(ENCODER.serialize(k)?),
+        Bound::Excluded(k) => Bound::Excluded(ENCODER.serialize(k)?),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    Ok(res)
+}
+
+impl DbInstance {
+    /// Iterate over the keys within the range, in the order described in the `scan` module
+    /// Reverse it with `.rev()` to start from the end of the range
+    pub fn range<K, V, R>(&self, range: R) -> Result<Iter<K, V>>
+    where
+        K: Serialize + DeserializeOwned,
+        V: DeserializeOwned,
+        R: RangeBounds<K>,
+    {
+        let start = encode_bound(range.start_bound())?;
+        let end = encode_bound(range.end_bound())?;
+
+        Ok(Iter::new(self.tree().range(start, end)))
+    }
+
+    /// Iterate over the keys starting with the serialized `prefix`
+    /// The prefix is usually the leading fields of the key as a tuple,
+    /// eg. `(name, gen)` for a key `{ name, gen, kind }`
+    pub fn scan_prefix<P, K, V>(&self, prefix: &P) -> Result<Iter<K, V>>
+    where
+        P: Serialize,
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let prefix = ENCODER.serialize(prefix)?;
+        Ok(Iter::new(self.tree().scan_prefix(&prefix)))
+    }
+
+    /// The entry with the smallest key
+    pub fn first<K, V>(&self) -> Result<Option<(K, V)>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        Ok(self.tree().first()?.and_then(decode_entry))
+    }
+
+    /// The entry with the biggest key
+    pub fn last<K, V>(&self) -> Result<Option<(K, V)>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        Ok(self.tree().last()?.and_then(decode_entry))
+    }
+
+    pub async fn range_async<K, V, R>(&self, range: R) -> Result<Vec<(K, V)>>
+    where
+        K: Serialize + DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+        R: RangeBounds<K>,
+    {
+        let start = encode_bound(range.start_bound())?;
+        let end = encode_bound(range.end_bound())?;
+
+        self.blocking(move |tree| Ok(Iter::<K, V>::new(tree.range(start, end)).collect()))
+            .await
+    }
+
+    pub async fn scan_prefix_async<P, K, V>(&self, prefix: &P) -> Result<Vec<(K, V)>>
+    where
+        P: Serialize,
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        let prefix = ENCODER.serialize(prefix)?;
+
+        self.blocking(move |tree| Ok(Iter::<K, V>::new(tree.scan_prefix(&prefix)).collect()))
+            .await
+    }
+
+    pub async fn first_async<K, V>(&self) -> Result<Option<(K, V)>>
+    where
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        self.blocking(|tree| Ok(tree.first()?.and_then(decode_entry)))
+            .await
+    }
+
+    pub async fn last_async<K, V>(&self) -> Result<Option<(K, V)>>
+    where
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        self.blocking(|tree| Ok(tree.last()?.and_then(decode_entry)))
+            .await
+    }
+}
+
+impl<K, V> DbTree<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    #[inline]
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Result<Iter<K, V>> {
+        self.untyped().range(range)
+    }
+
+    #[inline]
+    pub fn scan_prefix<P: Serialize>(&self, prefix: &P) -> Result<Iter<K, V>> {
+        self.untyped().scan_prefix(prefix)
+    }
+
+    #[inline]
+    pub fn first(&self) -> Result<Option<(K, V)>> {
+        self.untyped().first()
+    }
+
+    #[inline]
+    pub fn last(&self) -> Result<Option<(K, V)>> {
+        self.untyped().last()
+    }
+}
+
+impl<K, V> DbTree<K, V>
+where
+    K: Serialize + DeserializeOwned + Send + 'static,
+    V: Serialize + DeserializeOwned + Send + 'static,
+{
+    #[inline]
+    pub async fn range_async<R: RangeBounds<K>>(&self, range: R) -> Result<Vec<(K, V)>> {
+        self.untyped().range_async(range).await
+    }
+
+    #[inline]
+    pub async fn scan_prefix_async<P: Serialize>(&self, prefix: &P) -> Result<Vec<(K, V)>> {
+        self.untyped().scan_prefix_async(prefix).await
+    }
+
+    #[inline]
+    pub async fn first_async(&self) -> Result<Option<(K, V)>> {
+        self.untyped().first_async().await
+    }
+
+    #[inline]
+    pub async fn last_async(&self) -> Result<Option<(K, V)>> {
+        self.untyped().last_async().await
+    }
+}
@@ -0,0 +1,246 @@
+use crate::backend::{Backend, BackendTree, Bytes, RawBatch, RawEvent, RawIter, RawWatcher, TxTree};
+use crate::Result;
+use futures::channel::mpsc;
+use log::error;
+use sled::transaction::{
+    ConflictableTransactionError, TransactionError, Transactional, TransactionalTree,
+    UnabortableTransactionError,
+};
+use std::cell::RefCell;
+use std::error::Error;
+use std::ops::Bound;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The on-disk backend
+pub struct SledBackend(sled::Db);
+
+impl SledBackend {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let db = sled::Config::new()
+            .path(path)
+            .use_compression(true)
+            .open()?;
+
+        Ok(Self(db))
+    }
+
+    #[inline]
+    pub fn db(&self) -> &sled::Db {
+        &self.0
+    }
+}
+
+impl From<sled::Db> for SledBackend {
+    fn from(db: sled::Db) -> Self {
+        Self(db)
+    }
+}
+
+impl Backend for SledBackend {
+    fn open_tree(&self, name: &[u8]) -> Result<Arc<dyn BackendTree>> {
+        let tree = self.0.open_tree(name)?;
+        Ok(Arc::new(SledTree(tree)))
+    }
+
+    fn default_tree(&self) -> Arc<dyn BackendTree> {
+        Arc::new(SledTree((*self.0).clone()))
+    }
+
+    fn tree_names(&self) -> Vec<Bytes> {
+        self.0.tree_names().into_iter().map(|v| v.to_vec()).collect()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.0.flush()?;
+        Ok(())
+    }
+
+    fn transaction(
+        &self,
+        trees: &[Bytes],
+        f: &mut dyn FnMut(&[&dyn TxTree]) -> Result<()>,
+    ) -> Result<()> {
+        let trees = trees
+            .iter()
+            .map(|name| self.0.open_tree(name))
+            .collect::<sled::Result<Vec<_>>>()?;
+
+        // sled wants a `Fn`, the closure is only ever called from this thread
+        let f = RefCell::new(f);
+
+        let res = trees.as_slice().transaction(|views| {
+            let views: Vec<SledTxTree<'_>> = views.iter().map(SledTxTree).collect();
+            let views: Vec<&dyn TxTree> = views.iter().map(|v| v as &dyn TxTree).collect();
+
+            (f.borrow_mut())(&views).map_err(conflictable)
+        });
+
+        match res {
+            Ok(()) => Ok(()),
+            Err(TransactionError::Abort(why)) => Err(why),
+            Err(TransactionError::Storage(why)) => Err(why.into()),
+        }
+    }
+}
+
+/// Give the conflicts back to sled so it retries instead of aborting
+fn conflictable(
+    why: Box<dyn Error + Send + Sync>,
+) -> ConflictableTransactionError<Box<dyn Error + Send + Sync>> {
+    match why.downcast::<UnabortableTransactionError>() {
+        Ok(e) => ConflictableTransactionError::from(*e),
+        Err(e) => ConflictableTransactionError::Abort(e),
+    }
+}
+
+struct SledTxTree<'a>(&'a TransactionalTree);
+
+impl TxTree for SledTxTree<'_> {
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        Ok(self.0.get(key)?.map(|v| v.to_vec()))
+    }
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.0.insert(key, value)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<()> {
+        self.0.remove(key)?;
+        Ok(())
+    }
+}
+
+pub struct SledTree(pub(crate) sled::Tree);
+
+fn raw_iter(iter: sled::Iter) -> RawIter {
+    let iter = iter.map(|entry| {
+        entry
+            .map(|(k, v)| (k.to_vec(), v.to_vec()))
+            .map_err(|e| Box::new(e) as Box<_>)
+    });
+
+    Box::new(iter)
+}
+
+impl BackendTree for SledTree {
+    fn name(&self) -> Bytes {
+        self.0.name().to_vec()
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        Ok(self.0.get(key)?.map(|v| v.to_vec()))
+    }
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.0.insert(key, value)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<()> {
+        self.0.remove(key)?;
+        Ok(())
+    }
+
+    fn apply_batch(&self, batch: RawBatch) -> Result<()> {
+        let mut sled_batch = sled::Batch::default();
+
+        for (key, value) in batch {
+            match value {
+                Some(v) => sled_batch.insert(key, v),
+                None => sled_batch.remove(key),
+            }
+        }
+
+        self.0.apply_batch(sled_batch)?;
+        Ok(())
+    }
+
+    fn compare_and_swap(
+        &self,
+        key: &[u8],
+        old: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> Result<bool> {
+        let res = self.0.compare_and_swap(key, old, new)?;
+        Ok(res.is_ok())
+    }
+
+    fn range(&self, start: Bound<Bytes>, end: Bound<Bytes>) -> RawIter {
+        raw_iter(self.0.range((start, end)))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> RawIter {
+        raw_iter(self.0.scan_prefix(prefix))
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.0.clear()?;
+        Ok(())
+    }
+
+    fn watch_prefix(&self, prefix: &[u8]) -> Result<RawWatcher> {
+        let subscriber = self.0.watch_prefix(prefix);
+        let (sender, receiver) = mpsc::unbounded();
+
+        // The subscriber blocks while waiting, so it gets its own thread
+        // which stops on the first event after the receiver is dropped
+        std::thread::Builder::new()
+            .name(format!("watch-{}", String::from_utf8_lossy(&self.0.name())))
+            .spawn(move || {
+                for event in subscriber {
+                    let event = match event {
+                        sled::Event::Insert { key, value } => RawEvent::Insert {
+                            key: key.to_vec(),
+                            value: value.to_vec(),
+                        },
+
+                        sled::Event::Remove { key } => RawEvent::Remove { key: key.to_vec() },
+                    };
+
+                    if sender.unbounded_send(event).is_err() {
+                        break;
+                    }
+                }
+            })
+            .map_err(|why| {
+                error!("Cannot spawn the watcher thread | {}", why);
+                why
+            })?;
+
+        Ok(receiver)
+    }
+
+    fn first(&self) -> Result<Option<(Bytes, Bytes)>> {
+        Ok(self.0.first()?.map(|(k, v)| (k.to_vec(), v.to_vec())))
+    }
+
+    fn last(&self) -> Result<Option<(Bytes, Bytes)>> {
+        Ok(self.0.last()?.map(|(k, v)| (k.to_vec(), v.to_vec())))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_the_conflicts() {
+        let why: Box<dyn Error + Send + Sync> = Box::new(UnabortableTransactionError::Conflict);
+        assert!(matches!(conflictable(why), ConflictableTransactionError::Conflict));
+    }
+
+    #[test]
+    fn abort_on_the_other_errors() {
+        assert!(matches!(conflictable("abort".into()), ConflictableTransactionError::Abort(_)));
+    }
+}
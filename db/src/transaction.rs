@@ -0,0 +1,126 @@
+use crate::backend::{Bytes, TxTree};
+use crate::{DbInstance, DbTree, Result, ENCODER};
+use bincode::Options as _;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use std::marker::PhantomData;
+use tokio::task;
+
+/// The trees taking part in a transaction
+pub struct Transaction<'a> {
+    names: &'a [Bytes],
+    trees: &'a [&'a dyn TxTree],
+}
+
+impl<'a> Transaction<'a> {
+    /// Get the typed view of a tree passed to `DbInstance::transaction`
+    pub fn tree<K, V>(&self, tree: &DbTree<K, V>) -> Result<TransactionTree<'a, K, V>> {
+        self.untyped(tree.untyped())
+    }
+
+    /// Same as `tree`, for the trees without fixed types
+    pub fn untyped<K, V>(&self, tree: &DbInstance) -> Result<TransactionTree<'a, K, V>> {
+        Ok(TransactionTree {
+            inner: self.raw(tree)?,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The raw view of a tree, working on the serialized keys and values
+    pub fn raw(&self, tree: &DbInstance) -> Result<&'a dyn TxTree> {
+        let name = tree.tree().name();
+
+        let index = self
+            .names
+            .iter()
+            .position(|v| *v == name)
+            .ok_or_else(|| {
+                let name = String::from_utf8_lossy(&name);
+                format!("The tree {} is not a part of this transaction", name)
+            })?;
+
+        Ok(self.trees[index])
+    }
+}
+
+/// A typed tree inside of a transaction
+pub struct TransactionTree<'a, K, V> {
+    inner: &'a dyn TxTree,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> TransactionTree<'_, K, V>
+where
+    K: Serialize,
+    V: Serialize + DeserializeOwned,
+{
+    pub fn get(&self, key: &K) -> Result<Option<V>> {
+        let k = ENCODER.serialize(key)?;
+        let res = self
+            .inner
+            .get(&k)?
+            .and_then(|ref v| ENCODER.deserialize(v).ok());
+
+        Ok(res)
+    }
+
+    pub fn insert(&self, key: &K, value: &V) -> Result<()> {
+        let k = ENCODER.serialize(key)?;
+        let v = ENCODER.serialize(value)?;
+        self.inner.insert(&k, &v)
+    }
+
+    pub fn remove(&self, key: &K) -> Result<()> {
+        let k = ENCODER.serialize(key)?;
+        self.inner.remove(&k)
+    }
+}
+
+impl DbInstance {
+    /// Run `f` as a single transaction over the `trees`,
+    /// either all of its writes are applied or none of them when it returns an error
+    /// `f` may be run several times on conflicts, so it should not have any side effect
+    ///
+    /// ```ignore
+    /// db.transaction(&[reminders.untyped(), index.untyped()], |tx| {
+    ///     let reminders = tx.tree(&reminders)?;
+    ///     let index = tx.tree(&index)?;
+    ///     ...
+    /// })
+    /// ```
+    pub fn transaction<F, T>(&self, trees: &[&DbInstance], mut f: F) -> Result<T>
+    where
+        F: FnMut(&Transaction<'_>) -> Result<T>,
+    {
+        let names: Vec<Bytes> = trees.iter().map(|v| v.tree().name()).collect();
+        let mut res = None;
+
+        self.backend.transaction(&names, &mut |views| {
+            let tx = Transaction {
+                names: &names,
+                trees: views,
+            };
+
+            res = Some(f(&tx)?);
+            Ok(())
+        })?;
+
+        res.ok_or_else(|| "The transaction has not been run".into())
+    }
+
+    /// Same as `transaction`, run in the blocking thread pool
+    pub async fn transaction_async<F, T>(&self, trees: &[&DbInstance], f: F) -> Result<T>
+    where
+        F: FnMut(&Transaction<'_>) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let db = self.clone();
+        let trees: Vec<DbInstance> = trees.iter().map(|&v| v.clone()).collect();
+
+        task::spawn_blocking(move || {
+            let trees: Vec<&DbInstance> = trees.iter().collect();
+            db.transaction(&trees, f)
+        })
+        .await?
+    }
+}
@@ -0,0 +1,370 @@
+use crate::index::{index_tree_name, Index};
+use crate::{Batch, Bytes, DbInstance, Iter, IterKey, Result, ENCODER};
+use bincode::Options as _;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use std::iter;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio::task;
+
+/// A handle to a tree with its key and value types fixed
+/// Opened once with `DbInstance::open_tree`, so the types are checked at compile time
+/// instead of being repeated (and possibly mismatched) on every call
+pub struct DbTree<K, V> {
+    inner: DbInstance,
+    indexes: Vec<Arc<Index<K, V>>>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+// Manually implemented, the derive would require `K: Clone, V: Clone`
+impl<K, V> Clone for DbTree<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            indexes: self.indexes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> DbTree<K, V> {
+    pub(crate) fn new(inner: DbInstance) -> Self {
+        Self {
+            inner,
+            indexes: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the untyped instance of this tree
+    /// Writing through it (or through the TTL methods) bypasses the indexes
+    #[inline]
+    pub fn untyped(&self) -> &DbInstance {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        for index in &self.indexes {
+            index.tree.clear()?;
+        }
+
+        self.inner.clear()
+    }
+
+    pub async fn clear_async(&self) -> Result<()> {
+        for index in &self.indexes {
+            index.tree.clear_async().await?;
+        }
+
+        self.inner.clear_async().await
+    }
+
+    /// A batch cannot keep the indexes in sync, so it is refused on an indexed tree
+    pub fn batch(&self, batch: Batch) -> Result<()> {
+        self.check_unindexed()?;
+        self.inner.batch(batch)
+    }
+
+    pub async fn batch_async(&self, batch: Batch) -> Result<()> {
+        self.check_unindexed()?;
+        self.inner.batch_async(batch).await
+    }
+
+    fn check_unindexed(&self) -> Result<()> {
+        if self.indexes.is_empty() {
+            Ok(())
+        } else {
+            Err("Batches are not supported on an indexed tree, use insert and remove instead".into())
+        }
+    }
+
+    /// The instances of this tree and of all its indexes
+    fn trees(&self) -> Vec<&DbInstance> {
+        iter::once(&self.inner)
+            .chain(self.indexes.iter().map(|v| &v.tree))
+            .collect()
+    }
+}
+
+impl<K, V> DbTree<K, V>
+where
+    K: Serialize + DeserializeOwned + 'static,
+    V: Serialize + DeserializeOwned + 'static,
+{
+    /// Declare a secondary index, kept in sync by `insert` and `remove`
+    /// `f` gives the index keys of a record, a record can have any number of them
+    /// The index is built from the existing records the first time it is declared
+    ///
+    /// ```ignore
+    /// let reminders = db
+    ///     .open_tree::<i64, Reminder>("Reminders")?
+    ///     .with_index("user", |_, v| vec![v.user_id])?;
+    ///
+    /// let mine = reminders.get_by_index("user", &user_id)?;
+    /// ```
+    pub fn with_index<IK, F>(mut self, name: &str, f: F) -> Result<Self>
+    where
+        IK: Serialize,
+        F: Fn(&K, &V) -> Vec<IK> + Send + Sync + 'static,
+    {
+        let tree_name = String::from_utf8_lossy(&self.inner.tree().name()).into_owned();
+        let index_tree = self.inner.open(index_tree_name(&tree_name, name))?;
+        let index = Index::new(name, index_tree, f);
+
+        index.ensure_built(&self.inner)?;
+        self.indexes.push(Arc::new(index));
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn get(&self, key: &K) -> Result<Option<V>> {
+        self.inner.get(key)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<K, V> {
+        self.inner.get_all()
+    }
+
+    #[inline]
+    pub fn keys(&self) -> IterKey<K> {
+        self.inner.get_all_keys()
+    }
+
+    pub fn insert(&self, key: &K, value: &V) -> Result<()> {
+        if self.indexes.is_empty() {
+            return self.inner.insert(key, value);
+        }
+
+        let (k, v, entries) = self.prepare_insert(key, value)?;
+        self.insert_raw(&k, &v, &entries, false).map(|_| ())
+    }
+
+    /// Insert only if the key is not in the tree yet
+    /// Return whether the value has been inserted
+    pub fn insert_new(&self, key: &K, value: &V) -> Result<bool> {
+        let (k, v, entries) = self.prepare_insert(key, value)?;
+        self.insert_raw(&k, &v, &entries, true)
+    }
+
+    pub fn remove(&self, key: &K) -> Result<()> {
+        if self.indexes.is_empty() {
+            return self.inner.remove(key);
+        }
+
+        self.remove_raw(&[ENCODER.serialize(key)?])
+    }
+
+    pub fn remove_many<I: IntoIterator<Item = K>>(&self, keys: I) -> Result<()> {
+        if self.indexes.is_empty() {
+            return self.inner.remove_many(keys);
+        }
+
+        self.remove_raw(&serialize_keys(keys)?)
+    }
+
+    /// Serialize a record with its new index entries (one list per index)
+    #[allow(clippy::type_complexity)]
+    fn prepare_insert(&self, key: &K, value: &V) -> Result<(Bytes, Bytes, Vec<Vec<Bytes>>)> {
+        let k = ENCODER.serialize(key)?;
+        let v = ENCODER.serialize(value)?;
+        let mut entries = Vec::with_capacity(self.indexes.len());
+
+        for index in &self.indexes {
+            entries.push(index.entries(&k, key, value)?);
+        }
+
+        Ok((k, v, entries))
+    }
+
+    fn insert_raw(
+        &self,
+        k: &[u8],
+        v: &[u8],
+        new_entries: &[Vec<Bytes>],
+        only_new: bool,
+    ) -> Result<bool> {
+        self.inner.transaction(&self.trees(), |tx| {
+            let main = tx.raw(&self.inner)?;
+            let old = main.get(k)?;
+
+            if only_new && old.is_some() {
+                return Ok(false);
+            }
+
+            for (index, entries) in self.indexes.iter().zip(new_entries) {
+                let tree = tx.raw(&index.tree)?;
+
+                if let Some(old) = &old {
+                    for entry in index.raw_entries(k, old)? {
+                        tree.remove(&entry)?;
+                    }
+                }
+
+                for entry in entries {
+                    tree.insert(entry, &[])?;
+                }
+            }
+
+            main.insert(k, v)?;
+            Ok(true)
+        })
+    }
+
+    fn remove_raw(&self, keys: &[Bytes]) -> Result<()> {
+        self.inner.transaction(&self.trees(), |tx| {
+            let main = tx.raw(&self.inner)?;
+
+            for k in keys {
+                let old = match main.get(k)? {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                for index in &self.indexes {
+                    let tree = tx.raw(&index.tree)?;
+
+                    for entry in index.raw_entries(k, &old)? {
+                        tree.remove(&entry)?;
+                    }
+                }
+
+                main.remove(k)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Get every record having the `key` in the index `index`
+    /// Ordered by the keys of the records
+    pub fn get_by_index<IK: Serialize>(&self, index: &str, key: &IK) -> Result<Vec<(K, V)>> {
+        let index_key = ENCODER.serialize(key)?;
+        self.lookup(index, &index_key)
+    }
+
+    fn lookup(&self, name: &str, index_key: &[u8]) -> Result<Vec<(K, V)>> {
+        let index = self
+            .indexes
+            .iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| format!("No index named {}", name))?;
+
+        let mut res = Vec::new();
+
+        for entry in index.tree.tree().scan_prefix(index_key) {
+            let (entry, _) = entry?;
+            let raw_key = &entry[index_key.len()..];
+
+            let raw_value = match self.inner.tree().get(raw_key)? {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let k: K = ENCODER.deserialize(raw_key)?;
+            let v: V = ENCODER.deserialize(&raw_value)?;
+
+            // Skip the stale entries left by a write which bypassed the index
+            if index.entries(raw_key, &k, &v)?.contains(&entry) {
+                res.push((k, v));
+            }
+        }
+
+        Ok(res)
+    }
+
+    #[inline]
+    pub async fn get_async(&self, key: &K) -> Result<Option<V>> {
+        self.inner.get_async(key).await
+    }
+
+    pub async fn insert_async(&self, key: &K, value: &V) -> Result<()> {
+        if self.indexes.is_empty() {
+            return self.inner.insert_async(key, value).await;
+        }
+
+        let (k, v, entries) = self.prepare_insert(key, value)?;
+        let tree = self.clone();
+
+        task::spawn_blocking(move || tree.insert_raw(&k, &v, &entries, false))
+            .await?
+            .map(|_| ())
+    }
+
+    pub async fn insert_new_async(&self, key: &K, value: &V) -> Result<bool> {
+        let (k, v, entries) = self.prepare_insert(key, value)?;
+        let tree = self.clone();
+
+        task::spawn_blocking(move || tree.insert_raw(&k, &v, &entries, true)).await?
+    }
+
+    pub async fn remove_async(&self, key: &K) -> Result<()> {
+        if self.indexes.is_empty() {
+            return self.inner.remove_async(key).await;
+        }
+
+        let keys = vec![ENCODER.serialize(key)?];
+        let tree = self.clone();
+
+        task::spawn_blocking(move || tree.remove_raw(&keys)).await?
+    }
+
+    pub async fn remove_many_async<I: IntoIterator<Item = K>>(&self, keys: I) -> Result<()> {
+        if self.indexes.is_empty() {
+            return self.inner.remove_many_async(keys).await;
+        }
+
+        let keys = serialize_keys(keys)?;
+        let tree = self.clone();
+
+        task::spawn_blocking(move || tree.remove_raw(&keys)).await?
+    }
+
+    pub async fn get_by_index_async<IK: Serialize>(
+        &self,
+        index: &str,
+        key: &IK,
+    ) -> Result<Vec<(K, V)>>
+    where
+        K: Send,
+        V: Send,
+    {
+        let index_key = ENCODER.serialize(key)?;
+        let index = index.to_owned();
+        let tree = self.clone();
+
+        task::spawn_blocking(move || tree.lookup(&index, &index_key)).await?
+    }
+}
+
+impl<K, V> DbTree<K, V>
+where
+    K: Serialize + DeserializeOwned + Send + 'static,
+    V: Serialize + DeserializeOwned + Send + 'static,
+{
+    #[inline]
+    pub async fn get_all_async(&self) -> Result<Vec<(K, V)>> {
+        self.inner.get_all_async().await
+    }
+}
+
+fn serialize_keys<K: Serialize, I: IntoIterator<Item = K>>(keys: I) -> Result<Vec<Bytes>> {
+    let mut res = Vec::new();
+
+    for key in keys {
+        res.push(ENCODER.serialize(&key)?);
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,217 @@
+use crate::{DbInstance, DbTree, Result, ENCODER};
+use bincode::Options as _;
+use log::{error, info};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::ops::Bound;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::{self, JoinHandle};
+
+/// The index of every entry inserted with a TTL
+/// Keyed by the big endian expiry, then the tree name and the key,
+/// so the expired entries are always at the start of the tree
+pub const TTL_TREE: &str = "__ttl";
+
+/// A value stored with its expiry time (unix timestamp in seconds)
+/// A key should either always be written with a TTL or never,
+/// mixing both makes the plain values unreadable by the TTL methods
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Expiring<V> {
+    pub expires_at: u64,
+    pub value: V,
+}
+
+impl<V> Expiring<V> {
+    pub fn new(value: V, ttl: Duration) -> Self {
+        Self {
+            expires_at: unix_now().saturating_add(ttl.as_secs()),
+            value,
+        }
+    }
+
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= unix_now()
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_secs())
+        .unwrap_or(0)
+}
+
+fn index_key(expires_at: u64, tree: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    let mut data = expires_at.to_be_bytes().to_vec();
+    data.extend(ENCODER.serialize(&(tree, key))?);
+    Ok(data)
+}
+
+/// Read only the expiry of a stored `Expiring` value
+fn stored_expiry(bytes: &[u8]) -> Option<u64> {
+    ENCODER.allow_trailing_bytes().deserialize(bytes).ok()
+}
+
+impl DbInstance {
+    /// Insert a value which will be considered gone after the `ttl`
+    pub fn insert_with_ttl<K, V>(&self, key: &K, value: &V, ttl: Duration) -> Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let entry = Expiring::new(value, ttl);
+        let k = ENCODER.serialize(key)?;
+        let v = ENCODER.serialize(&entry)?;
+        let index = index_key(entry.expires_at, &self.tree().name(), &k)?;
+
+        // The index goes first, a stale index entry is harmless for the sweeper
+        self.backend.open_tree(TTL_TREE.as_bytes())?.insert(&index, &[])?;
+        self.tree().insert(&k, &v)
+    }
+
+    /// Get a value inserted by `insert_with_ttl`, `None` if it has expired
+    pub fn get_fresh<K, V>(&self, key: &K) -> Result<Option<V>>
+    where
+        K: Serialize,
+        V: DeserializeOwned,
+    {
+        let res = self
+            .get::<K, Expiring<V>>(key)?
+            .filter(|v| !v.is_expired())
+            .map(|v| v.value);
+
+        Ok(res)
+    }
+
+    pub async fn insert_with_ttl_async<K, V>(&self, key: &K, value: &V, ttl: Duration) -> Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let entry = Expiring::new(value, ttl);
+        let k = ENCODER.serialize(key)?;
+        let v = ENCODER.serialize(&entry)?;
+        let index = index_key(entry.expires_at, &self.tree().name(), &k)?;
+        let ttl_tree = self.backend.open_tree(TTL_TREE.as_bytes())?;
+
+        self.blocking(move |tree| {
+            ttl_tree.insert(&index, &[])?;
+            tree.insert(&k, &v)
+        })
+        .await
+    }
+
+    pub async fn get_fresh_async<K, V>(&self, key: &K) -> Result<Option<V>>
+    where
+        K: Serialize,
+        V: DeserializeOwned,
+    {
+        let res = self
+            .get_async::<K, Expiring<V>>(key)
+            .await?
+            .filter(|v| !v.is_expired())
+            .map(|v| v.value);
+
+        Ok(res)
+    }
+
+    /// Get the cached value, or fetch it then cache it for the `ttl`
+    pub async fn get_or_fetch<K, V, F, Fut>(&self, key: &K, ttl: Duration, fetch: F) -> Result<V>
+    where
+        K: Serialize,
+        V: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V>>,
+    {
+        if let Some(value) = self.get_fresh_async(key).await? {
+            return Ok(value);
+        }
+
+        let value = fetch().await?;
+        self.insert_with_ttl_async(key, &value, ttl).await?;
+        Ok(value)
+    }
+
+    /// Remove every expired entry of the whole database
+    /// Return the number of removed entries
+    pub fn sweep_expired(&self) -> Result<usize> {
+        let index = self.backend.open_tree(TTL_TREE.as_bytes())?;
+        let now = unix_now();
+        let end = now.saturating_add(1).to_be_bytes().to_vec();
+        let mut removed = 0;
+
+        for entry in index.range(Bound::Unbounded, Bound::Excluded(end)) {
+            let (idx, _) = entry?;
+            let (tree_name, key): (Vec<u8>, Vec<u8>) = ENCODER.deserialize(&idx[8..])?;
+            let tree = self.backend.open_tree(&tree_name)?;
+
+            if let Some(value) = tree.get(&key)? {
+                // The key may have been written again with a later expiry
+                if matches!(stored_expiry(&value), Some(v) if v <= now) {
+                    removed += tree.compare_and_swap(&key, Some(value.as_slice()), None)? as usize;
+                }
+            }
+
+            index.remove(&idx)?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Run `sweep_expired` periodically in the background
+    pub fn spawn_sweeper(&self, every: Duration) -> JoinHandle<()> {
+        let db = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(every).await;
+
+                let db = db.clone();
+                match task::spawn_blocking(move || db.sweep_expired()).await {
+                    Ok(Ok(0)) => {}
+                    Ok(Ok(n)) => info!("Swept {} expired entries from the database", n),
+                    Ok(Err(why)) => error!("Cannot sweep the expired entries | {}", why),
+                    Err(why) => error!("The sweeper panicked | {}", why),
+                }
+            }
+        })
+    }
+}
+
+/// For a cache tree, `V` is the type of the value without its expiry
+impl<K, V> DbTree<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    #[inline]
+    pub fn insert_with_ttl(&self, key: &K, value: &V, ttl: Duration) -> Result<()> {
+        self.untyped().insert_with_ttl(key, value, ttl)
+    }
+
+    #[inline]
+    pub fn get_fresh(&self, key: &K) -> Result<Option<V>> {
+        self.untyped().get_fresh(key)
+    }
+
+    #[inline]
+    pub async fn insert_with_ttl_async(&self, key: &K, value: &V, ttl: Duration) -> Result<()> {
+        self.untyped().insert_with_ttl_async(key, value, ttl).await
+    }
+
+    #[inline]
+    pub async fn get_fresh_async(&self, key: &K) -> Result<Option<V>> {
+        self.untyped().get_fresh_async(key).await
+    }
+
+    #[inline]
+    pub async fn get_or_fetch<F, Fut>(&self, key: &K, ttl: Duration, fetch: F) -> Result<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V>>,
+    {
+        self.untyped().get_or_fetch(key, ttl, fetch).await
+    }
+}
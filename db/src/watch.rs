@@ -0,0 +1,100 @@
+use crate::backend::RawEvent;
+use crate::{DbInstance, DbTree, Result, ENCODER};
+use bincode::Options as _;
+use futures::future;
+use futures::stream::{BoxStream, StreamExt};
+use log::error;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+/// A typed change made to a tree
+#[derive(Debug, Clone)]
+pub enum Event<K, V> {
+    Insert(K, V),
+    Remove(K),
+}
+
+impl<K, V> Event<K, V> {
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Insert(k, _) | Self::Remove(k) => k,
+        }
+    }
+}
+
+/// A stream of the changes of a tree, the events which cannot be decoded are skipped
+pub type Watcher<K, V> = BoxStream<'static, Event<K, V>>;
+
+fn decode<K, V>(event: RawEvent) -> Option<Event<K, V>>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    let res = match event {
+        RawEvent::Insert { key, value } => ENCODER
+            .deserialize(&key)
+            .and_then(|k| Ok(Event::Insert(k, ENCODER.deserialize(&value)?))),
+
+        RawEvent::Remove { key } => ENCODER.deserialize(&key).map(Event::Remove),
+    };
+
+    match res {
+        Ok(e) => Some(e),
+        Err(why) => {
+            error!("Cannot deserialize a change | {}", why);
+            None
+        }
+    }
+}
+
+impl DbInstance {
+    /// Subscribe to every later change of the tree
+    pub fn watch<K, V>(&self) -> Result<Watcher<K, V>>
+    where
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        self.watch_raw(&[])
+    }
+
+    /// Subscribe to the changes of the keys starting with the serialized `prefix`,
+    /// the prefix works the same as in `scan_prefix`
+    pub fn watch_prefix<P, K, V>(&self, prefix: &P) -> Result<Watcher<K, V>>
+    where
+        P: Serialize,
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        self.watch_raw(&ENCODER.serialize(prefix)?)
+    }
+
+    fn watch_raw<K, V>(&self, prefix: &[u8]) -> Result<Watcher<K, V>>
+    where
+        K: DeserializeOwned + Send + 'static,
+        V: DeserializeOwned + Send + 'static,
+    {
+        let stream = self
+            .tree()
+            .watch_prefix(prefix)?
+            .filter_map(|event| future::ready(decode(event)));
+
+        Ok(stream.boxed())
+    }
+}
+
+impl<K, V> DbTree<K, V>
+where
+    K: Serialize + DeserializeOwned + Send + 'static,
+    V: Serialize + DeserializeOwned + Send + 'static,
+{
+    #[inline]
+    pub fn watch(&self) -> Result<Watcher<K, V>> {
+        self.untyped().watch()
+    }
+
+    #[inline]
+    pub fn watch_prefix<P: Serialize>(&self, prefix: &P) -> Result<Watcher<K, V>> {
+        self.untyped().watch_prefix(prefix)
+    }
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::each_backend;
+use db::{Archive, Migrations, Schema};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NoteV1 {
+    text: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct NoteV2 {
+    text: String,
+    pinned: bool,
+}
+
+fn v1() -> Migrations {
+    Migrations::new().register(Schema::new::<u64, NoteV1>("notes", 1))
+}
+
+fn v2() -> Migrations {
+    let schema = Schema::new::<u64, NoteV2>("notes", 2)
+        .migrate(1, |old: NoteV1| NoteV2 {
+            text: old.text,
+            pinned: false,
+        });
+
+    Migrations::new().register(schema)
+}
+
+#[test]
+fn round_trip() {
+    each_backend(|backend| {
+        let db = backend.open();
+        v2().run(&db).unwrap();
+
+        let notes = db.open_tree::<u64, NoteV2, _>("notes").unwrap();
+        let note = NoteV2 {
+            text: "hello".into(),
+            pinned: true,
+        };
+
+        notes.insert(&1, &note).unwrap();
+        db.open("untyped").unwrap().insert(&"key", &42u8).unwrap();
+
+        let archive = Archive::export(&db, &v2(), |_| true).unwrap();
+        let json = serde_json::to_string(&archive).unwrap();
+        let archive: Archive = serde_json::from_str(&json).unwrap();
+
+        let restored = backend.open();
+        let report = archive.import(&restored, &v2(), false).unwrap();
+        assert!(report.has_changes());
+        assert!(report.migration.is_none());
+
+        let notes = restored.open_tree::<u64, NoteV2, _>("notes").unwrap();
+        assert_eq!(notes.get(&1).unwrap(), Some(note));
+
+        let untyped = restored.open("untyped").unwrap();
+        assert_eq!(untyped.get::<_, u8>(&"key").unwrap(), Some(42));
+
+        // Importing it again changes nothing
+        assert!(!archive.import(&restored, &v2(), false).unwrap().has_changes());
+    });
+}
+
+#[test]
+fn skip_the_broken_records() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let notes = db.open("notes").unwrap();
+        notes.insert(&1u64, &NoteV2 { text: "a".into(), pinned: false }).unwrap();
+        notes.insert(&2u64, &true).unwrap();
+
+        let archive = Archive::export(&db, &v2(), |_| true).unwrap();
+        assert_eq!(archive.trees["notes"].entries.len(), 1);
+        assert_eq!(archive.skipped(), 1);
+    });
+}
+
+#[test]
+fn dry_run_writes_nothing() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let notes = db.open_tree::<u64, NoteV2, _>("notes").unwrap();
+        notes.insert(&1, &NoteV2 { text: "a".into(), pinned: false }).unwrap();
+
+        let archive = Archive::export(&db, &v2(), |_| true).unwrap();
+
+        let target = backend.open();
+        let report = archive.import(&target, &v2(), true).unwrap();
+        assert!(report.has_changes());
+        assert!(target.open("notes").unwrap().is_empty());
+    });
+}
+
+#[test]
+fn migrate_an_older_archive() {
+    each_backend(|backend| {
+        let db = backend.open();
+        v1().run(&db).unwrap();
+
+        let notes = db.open_tree::<u64, NoteV1, _>("notes").unwrap();
+        notes.insert(&1, &NoteV1 { text: "old".into() }).unwrap();
+
+        let archive = Archive::export(&db, &v1(), |_| true).unwrap();
+        assert_eq!(archive.trees["notes"].version, Some(1));
+
+        let target = backend.open();
+        let report = archive.import(&target, &v2(), false).unwrap();
+        assert_eq!(report.migration.map(|v| v.quarantined()), Some(0));
+
+        let notes = target.open_tree::<u64, NoteV2, _>("notes").unwrap();
+        let expected = NoteV2 {
+            text: "old".into(),
+            pinned: false,
+        };
+
+        assert_eq!(notes.get(&1).unwrap(), Some(expected));
+    });
+}
+
+#[test]
+fn dry_run_an_older_archive() {
+    each_backend(|backend| {
+        let old = backend.open();
+        v1().run(&old).unwrap();
+
+        let notes = old.open_tree::<u64, NoteV1, _>("notes").unwrap();
+        notes.insert(&1, &NoteV1 { text: "same".into() }).unwrap();
+        notes.insert(&2, &NoteV1 { text: "new".into() }).unwrap();
+
+        let archive = Archive::export(&old, &v1(), |_| true).unwrap();
+
+        let db = backend.open();
+        v2().run(&db).unwrap();
+
+        let current = db.open_tree::<u64, NoteV2, _>("notes").unwrap();
+        current.insert(&1, &NoteV2 { text: "same".into(), pinned: false }).unwrap();
+
+        // Compared once migrated, so the first note is the same
+        let report = archive.import(&db, &v2(), true).unwrap();
+        let changes = report.trees.iter().find(|v| v.tree == "notes").unwrap();
+        assert_eq!(changes.unchanged, 1);
+        assert_eq!(changes.added, vec!["2"]);
+        assert!(changes.updated.is_empty());
+        assert_eq!(current.len(), 1);
+    });
+}
+
+#[test]
+fn refuse_a_newer_archive() {
+    each_backend(|backend| {
+        let db = backend.open();
+        v2().run(&db).unwrap();
+
+        let notes = db.open_tree::<u64, NoteV2, _>("notes").unwrap();
+        notes.insert(&1, &NoteV2 { text: "new".into(), pinned: true }).unwrap();
+
+        let archive = Archive::export(&db, &v2(), |_| true).unwrap();
+
+        let target = backend.open();
+        assert!(archive.import(&target, &v1(), false).is_err());
+        assert!(target.open("notes").unwrap().is_empty());
+    });
+}
@@ -0,0 +1,72 @@
+//! Every test runs once on each backend, sled living in a temporary directory
+
+use db::DbInstance;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Backend {
+    Memory,
+    Sled,
+}
+
+/// A fresh database, its directory is removed once it is dropped
+pub struct TestDb {
+    db: DbInstance,
+    _dir: Option<TempDir>,
+}
+
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+impl Backend {
+    pub fn open(self) -> TestDb {
+        match self {
+            Backend::Memory => TestDb {
+                db: DbInstance::memory(),
+                _dir: None,
+            },
+
+            Backend::Sled => {
+                let name = format!(
+                    "db-test-{}-{}",
+                    std::process::id(),
+                    NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+                );
+
+                let dir = std::env::temp_dir().join(name);
+                let db = DbInstance::new(&dir, None).expect("Cannot open sled in the temporary directory");
+
+                TestDb {
+                    db,
+                    _dir: Some(TempDir(dir)),
+                }
+            }
+        }
+    }
+}
+
+impl Deref for TestDb {
+    type Target = DbInstance;
+
+    fn deref(&self) -> &DbInstance {
+        &self.db
+    }
+}
+
+/// Run the test on the in-memory backend then on sled
+pub fn each_backend(test: impl Fn(Backend)) {
+    for backend in &[Backend::Memory, Backend::Sled] {
+        // Only shown when the test fails
+        println!("On the {:?} backend", backend);
+        test(*backend);
+    }
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::each_backend;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reminder {
+    user: u64,
+    text: String,
+}
+
+fn reminder(user: u64, text: &str) -> Reminder {
+    Reminder {
+        user,
+        text: text.into(),
+    }
+}
+
+#[test]
+fn follow_the_inserts_and_removals() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let reminders = db
+            .open_tree::<u64, Reminder, _>("reminders")
+            .unwrap()
+            .with_index("user", |_, v| vec![v.user])
+            .unwrap();
+
+        reminders.insert(&1, &reminder(7, "a")).unwrap();
+        reminders.insert(&2, &reminder(8, "b")).unwrap();
+        reminders.insert(&3, &reminder(7, "c")).unwrap();
+
+        let keys = |user: u64| -> Vec<u64> {
+            reminders
+                .get_by_index("user", &user)
+                .unwrap()
+                .into_iter()
+                .map(|(k, _)| k)
+                .collect()
+        };
+
+        assert_eq!(keys(7), vec![1, 3]);
+
+        // Moved to another user
+        reminders.insert(&1, &reminder(8, "a")).unwrap();
+        assert_eq!(keys(7), vec![3]);
+        assert_eq!(keys(8), vec![1, 2]);
+
+        reminders.remove(&2).unwrap();
+        assert_eq!(keys(8), vec![1]);
+
+        assert!(reminders.get_by_index("missing", &8u64).is_err());
+    });
+}
+
+#[test]
+fn build_from_the_existing_records() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let plain = db.open_tree::<u64, Reminder, _>("reminders").unwrap();
+        plain.insert(&1, &reminder(7, "a")).unwrap();
+        plain.insert(&2, &reminder(7, "b")).unwrap();
+
+        let reminders = plain.with_index("user", |_, v| vec![v.user]).unwrap();
+        assert_eq!(reminders.get_by_index("user", &7u64).unwrap().len(), 2);
+    });
+}
+
+#[test]
+fn refuse_a_batch() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let reminders = db
+            .open_tree::<u64, Reminder, _>("reminders")
+            .unwrap()
+            .with_index("user", |_, v| vec![v.user])
+            .unwrap();
+
+        let mut batch = db::Batch::new();
+        batch.insert(&1u64, &reminder(7, "a")).unwrap();
+        assert!(reminders.batch(batch).is_err());
+    });
+}
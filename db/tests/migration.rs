@@ -0,0 +1,110 @@
+mod common;
+
+use common::each_backend;
+use db::{DbInstance, Migrations, Schema, TreeMeta, META_TREE, QUARANTINE_SUFFIX};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UserV1 {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct UserV2 {
+    name: String,
+    level: u32,
+}
+
+fn migrations() -> Migrations {
+    let schema = Schema::new::<u64, UserV2>("users", 2)
+        .migrate(1, |old: UserV1| UserV2 {
+            name: old.name,
+            level: 1,
+        });
+
+    Migrations::new().register(schema)
+}
+
+fn version(db: &DbInstance, tree: &str) -> Option<u32> {
+    db.open_tree::<String, TreeMeta, _>(META_TREE)
+        .unwrap()
+        .get(&tree.to_owned())
+        .unwrap()
+        .map(|v| v.version)
+}
+
+#[test]
+fn upgrade_the_old_records() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let old = db.open_tree::<u64, UserV1, _>("users").unwrap();
+        old.insert(&1, &UserV1 { name: "kaede".into() }).unwrap();
+
+        let report = migrations().run(&db).unwrap();
+        assert_eq!(report.trees.len(), 1);
+        assert_eq!(report.trees[0].from, None);
+        assert_eq!(report.trees[0].migrated, 1);
+        assert_eq!(report.quarantined(), 0);
+
+        let users = db.open_tree::<u64, UserV2, _>("users").unwrap();
+        let expected = UserV2 {
+            name: "kaede".into(),
+            level: 1,
+        };
+
+        assert_eq!(users.get(&1).unwrap(), Some(expected));
+        assert_eq!(version(&db, "users"), Some(2));
+
+        // Already up to date, nothing is read again
+        assert!(migrations().run(&db).unwrap().is_empty());
+    });
+}
+
+#[test]
+fn quarantine_the_broken_records() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let users = db.open("users").unwrap();
+        users.insert(&1u64, &UserV1 { name: "kaede".into() }).unwrap();
+        users.insert(&2u64, &true).unwrap();
+
+        let report = migrations().run(&db).unwrap();
+        assert_eq!(report.quarantined(), 1);
+
+        let users = db.open_tree::<u64, UserV2, _>("users").unwrap();
+        assert_eq!(users.len(), 1);
+        assert!(users.get(&1).unwrap().is_some());
+
+        let quarantine = db.open(format!("users{}", QUARANTINE_SUFFIX)).unwrap();
+        assert_eq!(quarantine.get::<u64, bool>(&2).unwrap(), Some(true));
+    });
+}
+
+#[test]
+fn discard_the_old_cache() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let cache = db.open("cache").unwrap();
+        cache.insert(&1u64, &"old".to_owned()).unwrap();
+
+        let migrations = Migrations::new().register(Schema::new::<u64, u64>("cache", 2).discard(1));
+        let report = migrations.run(&db).unwrap();
+
+        assert_eq!(report.trees[0].discarded, 1);
+        assert_eq!(report.quarantined(), 0);
+        assert!(cache.is_empty());
+    });
+}
+
+#[test]
+fn refuse_a_newer_tree() {
+    each_backend(|backend| {
+        let db = backend.open();
+        db.open_tree::<String, TreeMeta, _>(META_TREE)
+            .unwrap()
+            .insert(&"users".to_owned(), &TreeMeta { version: 3 })
+            .unwrap();
+
+        assert!(migrations().run(&db).is_err());
+    });
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::each_backend;
+
+#[test]
+fn range_in_key_order() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let tree = db.open_tree::<u64, u64, _>("numbers").unwrap();
+
+        for i in [300, 5, 70_000, 42, 1].iter() {
+            tree.insert(i, &(i * 2)).unwrap();
+        }
+
+        let keys: Vec<u64> = tree.range(..).unwrap().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![1, 5, 42, 300, 70_000]);
+
+        let keys: Vec<u64> = tree.range(5..=300).unwrap().rev().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![300, 42, 5]);
+
+        assert_eq!(tree.first().unwrap(), Some((1, 2)));
+        assert_eq!(tree.last().unwrap(), Some((70_000, 140_000)));
+    });
+}
+
+#[test]
+fn inverted_range_is_empty() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let tree = db.open_tree::<u64, u64, _>("numbers").unwrap();
+        tree.insert(&1, &1).unwrap();
+        tree.insert(&10, &10).unwrap();
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = tree.range(10..1).unwrap();
+        assert_eq!(inverted.count(), 0);
+    });
+}
+
+#[test]
+fn tags_by_guild() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let tags = db.open_tree::<(u64, String), String, _>("tags").unwrap();
+
+        tags.insert(&(1, "zeta".into()), &"a".into()).unwrap();
+        tags.insert(&(2, "alpha".into()), &"b".into()).unwrap();
+        tags.insert(&(1, "alpha".into()), &"c".into()).unwrap();
+        tags.insert(&(256, "beta".into()), &"d".into()).unwrap();
+
+        let names: Vec<String> = tags
+            .scan_prefix(&1u64)
+            .unwrap()
+            .map(|((_, name), _)| name)
+            .collect();
+
+        // The strings sort by length first
+        assert_eq!(names, vec!["zeta", "alpha"]);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let other = runtime.block_on(tags.scan_prefix_async(&256u64)).unwrap();
+        assert_eq!(other, vec![((256, "beta".into()), "d".into())]);
+    });
+}
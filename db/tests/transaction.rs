@@ -0,0 +1,93 @@
+mod common;
+
+use common::each_backend;
+use std::error::Error;
+
+#[test]
+fn commit_every_tree() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let a = db.open_tree::<u8, u32, _>("a").unwrap();
+        let b = db.open_tree::<u8, u32, _>("b").unwrap();
+
+        db.transaction(&[a.untyped(), b.untyped()], |tx| {
+            tx.tree(&a)?.insert(&1, &10)?;
+            tx.tree(&b)?.insert(&1, &20)?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(a.get(&1).unwrap(), Some(10));
+        assert_eq!(b.get(&1).unwrap(), Some(20));
+    });
+}
+
+#[test]
+fn roll_back_on_error() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let a = db.open_tree::<u8, u32, _>("a").unwrap();
+        let b = db.open_tree::<u8, u32, _>("b").unwrap();
+        a.insert(&1, &1).unwrap();
+
+        let res: Result<(), Box<dyn Error + Send + Sync>> = db.transaction(&[a.untyped(), b.untyped()], |tx| {
+            let a = tx.tree(&a)?;
+            a.insert(&1, &2)?;
+            a.insert(&2, &2)?;
+            tx.tree(&b)?.insert(&1, &2)?;
+            Err("abort".into())
+        });
+
+        assert!(res.is_err());
+        assert_eq!(a.get(&1).unwrap(), Some(1));
+        assert_eq!(a.get(&2).unwrap(), None);
+        assert!(b.is_empty());
+    });
+}
+
+#[test]
+fn refuse_a_tree_outside_of_it() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let a = db.open_tree::<u8, u32, _>("a").unwrap();
+        let b = db.open_tree::<u8, u32, _>("b").unwrap();
+
+        let res = db.transaction(&[a.untyped()], |tx| tx.tree(&b)?.insert(&1, &1));
+
+        assert!(res.is_err());
+        assert!(b.is_empty());
+    });
+}
+
+#[test]
+fn retry_the_conflicts() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let counter = db.open_tree::<u8, u32, _>("counter").unwrap();
+
+        // Every thread reads then writes the same key, sled makes the losers start again
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let db = db.clone();
+                let counter = counter.clone();
+
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        db.transaction(&[counter.untyped()], |tx| {
+                            let tree = tx.tree(&counter)?;
+                            let n = tree.get(&0)?.unwrap_or(0);
+                            tree.insert(&0, &(n + 1))
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(counter.get(&0).unwrap(), Some(200));
+    });
+}
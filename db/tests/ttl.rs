@@ -0,0 +1,57 @@
+mod common;
+
+use common::each_backend;
+use std::time::Duration;
+
+#[test]
+fn sweep_only_the_expired_entries() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let cache = db.open_tree::<String, u32, _>("cache").unwrap();
+
+        cache.insert_with_ttl(&"old".into(), &1, Duration::from_secs(0)).unwrap();
+        cache.insert_with_ttl(&"new".into(), &2, Duration::from_secs(3600)).unwrap();
+
+        assert_eq!(cache.get_fresh(&"old".into()).unwrap(), None);
+        assert_eq!(cache.get_fresh(&"new".into()).unwrap(), Some(2));
+
+        assert_eq!(db.sweep_expired().unwrap(), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(db.sweep_expired().unwrap(), 0);
+    });
+}
+
+#[test]
+fn keep_an_entry_written_again() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let cache = db.open_tree::<String, u32, _>("cache").unwrap();
+
+        cache.insert_with_ttl(&"key".into(), &1, Duration::from_secs(0)).unwrap();
+        cache.insert_with_ttl(&"key".into(), &2, Duration::from_secs(3600)).unwrap();
+
+        assert_eq!(db.sweep_expired().unwrap(), 0);
+        assert_eq!(cache.get_fresh(&"key".into()).unwrap(), Some(2));
+    });
+}
+
+#[test]
+fn fetch_only_when_missing() {
+    each_backend(|backend| {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let db = backend.open();
+        let cache = db.open_tree::<u8, String, _>("cache").unwrap();
+        let ttl = Duration::from_secs(3600);
+
+        runtime.block_on(async {
+            let first = cache.get_or_fetch(&1, ttl, || async { Ok("fetched".into()) });
+            assert_eq!(first.await.unwrap(), "fetched");
+
+            let second = cache.get_or_fetch(&1, ttl, || async { Err("fetched again".into()) });
+            assert_eq!(second.await.unwrap(), "fetched");
+        });
+    });
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::each_backend;
+use db::Event;
+use futures::executor::block_on;
+use futures::StreamExt;
+
+#[test]
+fn receive_the_changes() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let tree = db.open_tree::<u64, String, _>("tree").unwrap();
+        let mut watcher = tree.watch().unwrap();
+
+        tree.insert(&1, &"one".into()).unwrap();
+        tree.remove(&1).unwrap();
+
+        match block_on(watcher.next()) {
+            Some(Event::Insert(1, v)) => assert_eq!(v, "one"),
+            e => panic!("Unexpected event {:?}", e),
+        }
+
+        match block_on(watcher.next()) {
+            Some(Event::Remove(1)) => {}
+            e => panic!("Unexpected event {:?}", e),
+        }
+    });
+}
+
+#[test]
+fn receive_only_the_prefix() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let tags = db.open_tree::<(u64, String), u8, _>("tags").unwrap();
+        let mut watcher = tags.watch_prefix(&2u64).unwrap();
+
+        tags.insert(&(1, "a".into()), &1).unwrap();
+        tags.insert(&(2, "b".into()), &2).unwrap();
+
+        match block_on(watcher.next()) {
+            Some(e) => assert_eq!(e.key(), &(2, "b".to_owned())),
+            None => panic!("The watcher has ended"),
+        }
+    });
+}
+
+#[test]
+fn receive_every_change_in_order() {
+    each_backend(|backend| {
+        let db = backend.open();
+        let tree = db.open_tree::<u64, u64, _>("tree").unwrap();
+        let watcher = tree.watch().unwrap();
+
+        for i in 0..100 {
+            tree.insert(&i, &(i * 2)).unwrap();
+        }
+
+        let keys: Vec<u64> = block_on(watcher.take(100).map(|e| *e.key()).collect());
+        assert_eq!(keys, (0..100).collect::<Vec<_>>());
+    });
+}
+
+/// The threads of this process with this name
+#[cfg(target_os = "linux")]
+fn threads_named(name: &str) -> usize {
+    std::fs::read_dir("/proc/self/task")
+        .unwrap()
+        .filter_map(|v| std::fs::read_to_string(v.ok()?.path().join("comm")).ok())
+        .filter(|v| v.trim_end() == name)
+        .count()
+}
+
+/// Wait up to 2 seconds for `count` threads with this name
+#[cfg(target_os = "linux")]
+fn wait_for_threads(name: &str, count: usize) -> bool {
+    for _ in 0..100 {
+        if threads_named(name) == count {
+            return true;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    false
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn stop_the_thread_once_dropped() {
+    let db = common::Backend::Sled.open();
+    let tree = db.open_tree::<u64, u64, _>("stopping").unwrap();
+    let watcher = tree.watch().unwrap();
+    assert!(wait_for_threads("watch-stopping", 1), "The watcher thread has not started");
+
+    // The thread only notices on the next change
+    drop(watcher);
+    tree.insert(&1, &1).unwrap();
+    assert!(wait_for_threads("watch-stopping", 0), "The watcher thread is still running");
+}
@@ -12,6 +12,7 @@ pub_import_all! {
     //music,
     pokemon,
     rgb,
+    tags,
     utility,
     osu,
 }
@@ -1,3 +1,4 @@
+use crate::slash::SlashCommand;
 use serenity::framework::standard::macros::group;
 use magic::import_all;
 
@@ -12,8 +13,11 @@ import_all! {
     love,
     info,
     invite,
+    stats,
 }
 
 #[group]
-#[commands(avatar, say, love, choose, ping, kanji, urban, invite, info, uptime)]
+#[commands(avatar, say, love, choose, ping, kanji, urban, invite, info, uptime, stats)]
 struct General;
+
+pub static GENERAL_SLASH_COMMANDS: &[&SlashCommand] = &[&AVATAR_SLASH, &KANJI_SLASH, &URBAN_SLASH];
@@ -1,6 +1,24 @@
 use crate::commands::prelude::*;
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
 use crate::utils::get_dominant_color;
-use futures::future::{self, TryFutureExt};
+use futures::future::{self, FutureExt as _};
+use serenity::builder::CreateEmbed;
+use serenity::model::user::User;
+
+pub static AVATAR_SLASH: SlashCommand = SlashCommand {
+    command: &AVATAR_COMMAND,
+    name: None,
+    options: &[SlashOption {
+        name: "user",
+        description: "Yourself if not given",
+        kind: OptionKind::User,
+        required: false,
+    }],
+    run: |ctx, origin, args| {
+        let user = args.user("user").unwrap_or_else(|| origin.author());
+        show_avatar(ctx, origin, user).boxed()
+    },
+};
 
 #[command]
 #[aliases("ava")]
@@ -11,6 +29,10 @@ use futures::future::{self, TryFutureExt};
 /// If none, I will response with the user's avatar
 async fn avatar(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
     let user = msg.mentions.get(0).unwrap_or(&msg.author);
+    show_avatar(ctx, &Origin::from(msg), user).await
+}
+
+async fn show_avatar(ctx: &Context, origin: &Origin<'_>, user: &User) -> CommandResult {
     let display_name = format!("{}#{:04}", user.name, user.discriminator);
     let avatar = user
         .avatar_url()
@@ -24,33 +46,27 @@ async fn avatar(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
         a.join(".")
     };
     
-    let mess = msg.channel_id.send_message(&ctx, |m| {
-        m.embed(|embed| {
-            embed
-                .title(display_name.to_owned())
-                .image(avatar.to_owned())
-                .timestamp(Utc::now().to_rfc3339())
-        })
-    });
-    
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(display_name.to_owned())
+        .image(avatar.to_owned())
+        .timestamp(Utc::now().to_rfc3339());
+
+    let mess = origin.send_embed(ctx, embed);
     let color = get_dominant_color(&static_avatar);
    
-    let (color, mut message) = future::try_join(
-        color,
-        mess.map_err(|e| Box::new(e) as Box<_>)
-    ).await?;
+    let (color, mut message) = future::try_join(color, mess).await?;
      
     info!("the dominanted color is {:?}", &color);
      
-    message.edit(ctx, move |m| {
-        m.embed(move |embed| {
-            embed
-                .color(color)
-                .title(display_name)
-                .image(avatar)
-                .timestamp(Utc::now().to_rfc3339())
-        })
-    }).await?;
+    let mut embed = CreateEmbed::default();
+    embed
+        .color(color)
+        .title(display_name)
+        .image(avatar)
+        .timestamp(Utc::now().to_rfc3339());
+
+    origin.edit_embed(ctx, &mut message, embed).await?;
     
     Ok(())
 }
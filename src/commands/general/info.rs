@@ -4,8 +4,8 @@ use crate::traits::ChannelExt;
 #[command]
 #[aliases("information")]
 async fn info(ctx: &Context, msg: &Message, _arg: Args) -> CommandResult {
-    let data = ctx.data.read().await;
-    let user_info = data.get::<InforKey>().unwrap();
+    let db = get_db(ctx).await?;
+    let executed = crate::stats::count(&db).await?;
     let my_info = ctx.http.get_current_application_info().await?;
 
     let description = format!(
@@ -18,7 +18,7 @@ async fn info(ctx: &Context, msg: &Message, _arg: Args) -> CommandResult {
     
     msg.channel_id.send_embed(ctx)
         .with_description(description)
-        .with_field("Executed commands", user_info.executed_commands(), true)
+        .with_field("Executed commands", executed, true)
         .await?;
 
     Ok(())
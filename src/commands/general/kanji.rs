@@ -1,7 +1,21 @@
 use crate::commands::prelude::*;
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
 use crate::MaziiApi as _;
+use futures::future::FutureExt as _;
 use magic::traits::MagicStr as _;
-use crate::traits::ChannelExt;
+use serenity::builder::CreateEmbed;
+
+pub static KANJI_SLASH: SlashCommand = SlashCommand {
+    command: &KANJI_COMMAND,
+    name: None,
+    options: &[SlashOption {
+        name: "kanji",
+        description: "One or more kanji",
+        kind: OptionKind::String,
+        required: true,
+    }],
+    run: |ctx, origin, args| explain(ctx, origin, args.string("kanji").unwrap_or_default()).boxed(),
+};
 
 #[command]
 #[aliases("k")]
@@ -10,14 +24,15 @@ use crate::traits::ChannelExt;
 /// Get the details meaning of kanji(s)
 async fn kanji(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     msg.channel_id.broadcast_typing(&ctx).await?;
-    let content = args.rest();
+    explain(ctx, &Origin::from(msg), args.rest()).await
+}
+
+async fn explain(ctx: &Context, origin: &Origin<'_>, content: &str) -> CommandResult {
     let reqwest = get_data::<ReqwestClient>(&ctx).await.unwrap();
     let kanjis = reqwest.kanji(&content).await?;
     
-    let mut embed = msg
-        .channel_id
-        .send_embed(ctx)
-        .with_color(0x977df2);
+    let mut embed = CreateEmbed::default();
+    embed.color(0x977df2);
 
     for kanji in kanjis {
         let info = format!(
@@ -37,37 +52,10 @@ async fn kanji(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
             .and_then(|d| d.split_at_limit(1024, "\n").next().map(String::from))
             .unwrap_or_default();
     
-        embed = embed.with_field(info, detail, false);
+        embed.field(info, detail, false);
     }
     
-    embed.await?;
-    
-    // msg.channel_id.send_message(ctx, |m| m.embed(|embed| {
-    //     embed.color(0x977df2);
-    //     
-    //     for kanji in kanjis {
-    //         let info = format!(
-    //             "{} - {level} {mean} | {on} {kun}",
-    //             kanji.kanji,
-    //             mean = kanji.mean,
-    //             on = kanji.normal_on(),
-    //             level = kanji.level.map(|l| format!("(N{})", l)).unwrap_or_default(),
-    //             kun = kanji
-    //                 .normal_kun()
-    //                 .map(|k| format!("| {}", k))
-    //                 .unwrap_or_default()
-    //         );
-    // 
-    //         let detail = kanji
-    //             .normal_detail()
-    //             .and_then(|d| d.split_at_limit(1024, "\n").next().map(String::from))
-    //             .unwrap_or_default();
-    // 
-    //         embed.field(info, detail, false);
-    //     }
-   // 
-    //     embed
-    // })).await?;
+    origin.send_embed(ctx, embed).await?;
     
     Ok(())
 }
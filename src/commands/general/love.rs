@@ -26,26 +26,17 @@ async fn love(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
         mem::swap(&mut person, &mut person2);
     }
     
-    let config = crate::read_config().await;
-    let color = config.color.lovely;
-    drop(config);
+    let mut embed = themed_embed(msg.guild_id, |c| c.lovely).await;
+    embed.title("Thước đo tình yêu");
+    embed.description(format!(
+        ":sparkling_heart: **{}**\n:sparkling_heart: **{}**",
+        person.name, person2.name
+    ));
+    embed.thumbnail(HEART_URL);
+    embed.field(&point_str, progress_bar(point, 18), false);
+    embed.field(&point_str, get_msg(point), false);
 
-    msg.channel_id.send_message(&ctx.http, |m| {
-        m.embed(|embed| {
-            embed.title("Thước đo tình yêu");
-            embed.description(format!(
-                ":sparkling_heart: **{}**\n:sparkling_heart: **{}**",
-                person.name, person2.name
-            ));
-            embed.thumbnail(HEART_URL);
-            embed.field(&point_str, progress_bar(point, 18), false);
-            embed.field(&point_str, get_msg(point), false);
-            
-            embed.color(color);
-            
-            embed
-        })
-    }).await?;
+    msg.channel_id.send_message(&ctx.http, |m| m.set_embed(embed)).await?;
 
     Ok(())
 }
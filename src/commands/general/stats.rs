@@ -0,0 +1,90 @@
+use crate::commands::prelude::*;
+use crate::stats::{self, Usage};
+use core::time::Duration;
+use magic::traits::MagicIter as _;
+
+/// How many commands are shown
+const TOP: usize = 10;
+
+#[command]
+#[aliases("statistics")]
+#[usage = "?[global] ?[window]"]
+#[example = "global 30d"]
+/// Show the most used commands, their error rates and how long they take
+/// in this server (or everywhere with __global__) over the last 7 days,
+/// or over another __window__ like `24h`, `30d` or `all`
+/// Only the owners of the bot can see __global__ and `all`
+async fn stats(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let mut global = msg.guild_id.is_none();
+    let mut window = Some(Duration::from_secs(7 * 24 * 60 * 60));
+
+    for arg in args.raw() {
+        match arg.to_lowercase().as_str() {
+            "global" | "all_servers" => global = true,
+            "all" | "forever" => window = None,
+            v => match humantime::parse_duration(v) {
+                Ok(d) => window = Some(d),
+                Err(_) => {
+                    let mess = format!("`{}` is neither __global__ nor a time window like `7d`", arg);
+                    msg.channel_id.say(ctx, mess).await?;
+                    return Ok(());
+                }
+            },
+        }
+    }
+
+    let is_owner = get_data::<OwnersKey>(ctx)
+        .await
+        .map_or(false, |v| v.contains(&msg.author.id));
+
+    // The other servers are none of their business, and all the records are a lot to read
+    if (global || window.is_none()) && !is_owner {
+        let mess = "Only the owners of the bot can see the usage everywhere or since the beginning";
+        msg.channel_id.say(ctx, mess).await?;
+        return Ok(());
+    }
+
+    let guild = if global { None } else { msg.guild_id };
+    let db = get_db(ctx).await?;
+    let summary = stats::summary(&db, window, guild).await?;
+
+    let scope = if global { "everywhere" } else { "in this server" };
+    let period = match window {
+        Some(w) => format!("over the last {}", humantime::format_duration(w)),
+        None => String::from("since the beginning"),
+    };
+
+    let top = summary
+        .commands
+        .iter()
+        .take(TOP)
+        .zip(1..)
+        .map(|((name, usage), i)| format!("**{}.** `{}` {}", i, name, describe(usage)))
+        .join('\n');
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title(format!("Command usage {} {}", scope, period));
+    embed.timestamp(now());
+
+    if summary.total.uses == 0 {
+        embed.description("No command has been used yet");
+    } else {
+        embed.description(format!("All commands: {}", describe(&summary.total)));
+        embed.field(format!("Top {} commands", TOP.min(summary.commands.len())), top, false);
+    }
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
+
+fn describe(usage: &Usage) -> String {
+    format!(
+        "{} uses, {:.1}% errors, p50 {}ms / p95 {}ms / p99 {}ms",
+        usage.uses,
+        usage.error_rate(),
+        usage.percentile(50),
+        usage.percentile(95),
+        usage.percentile(99),
+    )
+}
@@ -15,22 +15,14 @@ async fn uptime(ctx: &Context, msg: &Message, _args: Args) -> CommandResult {
         .unwrap()
         .uptime();
         
-    let config = crate::read_config().await;
-    let color = config.color.information;
-    drop(config);
-
     let message = format!("I have been up for **{}**", format_duration(uptime));
-    msg.channel_id.send_message(&ctx.http, |m| {
-        m.embed(|embed| {
-            embed.title("Uptime");
-            embed.description(message);
-            embed.timestamp(now.to_rfc3339());
-            
-            embed.color(color);
-            
-            embed
-        })
-    }).await?;
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title("Uptime");
+    embed.description(message);
+    embed.timestamp(now.to_rfc3339());
+
+    msg.channel_id.send_message(&ctx.http, |m| m.set_embed(embed)).await?;
 
     Ok(())
 }
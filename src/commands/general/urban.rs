@@ -1,5 +1,19 @@
 use crate::commands::prelude::*;
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
 use crate::UrbanApi as _;
+use futures::future::FutureExt as _;
+
+pub static URBAN_SLASH: SlashCommand = SlashCommand {
+    command: &URBAN_COMMAND,
+    name: None,
+    options: &[SlashOption {
+        name: "word",
+        description: "A random word if not given",
+        kind: OptionKind::String,
+        required: false,
+    }],
+    run: |ctx, origin, args| search(ctx, origin, args.string("word").unwrap_or_default()).boxed(),
+};
 
 #[command]
 #[aliases("u")]
@@ -8,7 +22,10 @@ use crate::UrbanApi as _;
 /// Search the UrbanDictionary for a meaning of a slang word
 async fn urban(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     msg.channel_id.broadcast_typing(&ctx).await?;
-    let word = args.rest();
+    search(ctx, &Origin::from(msg), args.rest()).await
+}
+
+async fn search(ctx: &Context, origin: &Origin<'_>, word: &str) -> CommandResult {
     let reqwest = get_data::<ReqwestClient>(&ctx).await.unwrap();
     let result = if word.is_empty() {
         reqwest.get_random().await?
@@ -16,34 +33,29 @@ async fn urban(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         reqwest.search_word(&word).await?
     };
     
-    let config = crate::read_config().await;
-    let err_color = config.color.error;
-    let suc_color = config.color.information;
-    drop(config);
-
-    msg.channel_id.send_message(ctx, move |m| m.embed(|embed| {
-        match result.get(0) {
-            Some(u) => {
-                embed.title(format!("Definition of {}", &u.word));
-                embed.description(&u.definition);
-                embed.url(&u.permalink);
-                embed.color(suc_color);
-                embed.author(|author| author.name(&u.author));
-                embed.timestamp(u.written_on.to_owned());
-                embed.field("Example", &u.example, false);
-                embed.field(":thumbsup:", u.thumbs_up, true);
-                embed.field(":thumbsdown:", u.thumbs_down, true);
-            }
-            
-            None => {
-                embed.title(format!("Definition of {}", word));
-                embed.description("404 Not Found");
-                embed.color(err_color);
-            }
+    let embed = match result.get(0) {
+        Some(u) => {
+            let mut embed = themed_embed(origin.guild_id(), |c| c.information).await;
+            embed.title(format!("Definition of {}", &u.word));
+            embed.description(&u.definition);
+            embed.url(&u.permalink);
+            embed.author(|author| author.name(&u.author));
+            embed.timestamp(u.written_on.to_owned());
+            embed.field("Example", &u.example, false);
+            embed.field(":thumbsup:", u.thumbs_up, true);
+            embed.field(":thumbsdown:", u.thumbs_down, true);
+            embed
         }
         
-        embed
-    })).await?;
+        None => {
+            let mut embed = themed_embed(origin.guild_id(), |c| c.error).await;
+            embed.title(format!("Definition of {}", word));
+            embed.description("404 Not Found");
+            embed
+        }
+    };
+
+    origin.send_embed(ctx, embed).await?;
 
     Ok(())
 }
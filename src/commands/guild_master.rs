@@ -2,16 +2,31 @@ use serenity::framework::standard::macros::group;
 use magic::import_all;
 
 import_all! {
-    repeat_words,
-    find_sauce,
-    // find_sadkaede,
-    prefix,
-    logger
+    list,
+    get,
+    set,
+    reset,
+    add,
+    remove,
+    enable,
+    disable,
+    inherit,
+    toggles,
+    export,
+    import,
+    allow,
+    deny,
+    unperm,
+    perms
 }
 
 #[group]
 #[prefixes("guild_option", "option", "opt")]
 #[only_in("guilds")]
 #[owner_privilege]
-#[sub_groups(Prefix, Logger, FindSauce, RepeatWords)]
+#[commands(
+    list, get, set, reset, add, remove, enable, disable, inherit, toggles, export, import, allow,
+    deny, unperm, perms
+)]
+#[default_command(list)]
 struct GuildMaster;
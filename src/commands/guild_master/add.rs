@@ -0,0 +1,44 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[min_args(2)]
+#[usage = "{option} {values}"]
+#[example = "find_sauce.channels #art #memes"]
+/// Add some channels, words or roles to an option holding a list of them
+async fn add(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let key = args.single::<String>()?;
+    let setting = match guild_settings::find_writable(ctx, msg, &key).await? {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let items = match setting.kind.parse(ctx, msg, args.rest()).await {
+        Ok(v) if v.is_collection() => v,
+        Ok(_) => {
+            let mess = format!("`{}` is not a list, use `option set` instead", setting.key);
+            msg.channel_id.say(ctx, mess).await?;
+            return Ok(());
+        }
+
+        Err(why) => {
+            msg.channel_id.say(ctx, why).await?;
+            return Ok(());
+        }
+    };
+
+    let value = guild_settings::update(ctx, guild_id, setting, move |v| {
+        v.extend(items.clone())
+    })
+    .await?;
+
+    guild_settings::send(ctx, msg, setting, &value).await?;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_GUILD)]
+#[min_args(2)]
+#[usage = "{name} {@users|@roles|#channels|everyone}"]
+#[example = "nhentai @Adults"]
+/// Allow a command or a command group to the mentioned users, roles or channels,
+/// even when it is denied to a broader target, or to `everyone`
+async fn allow(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let name = args.single::<String>()?;
+    let targets = guild_settings::parse_targets(ctx, msg, args.rest()).await?;
+    guild_settings::set_rule(ctx, msg, &name, &targets, Some(true)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_GUILD)]
+#[min_args(2)]
+#[usage = "{name} {@users|@roles|#channels|everyone}"]
+#[example = "prune @Members #general"]
+/// Deny a command or a command group to the mentioned users, roles or channels,
+/// or to `everyone`, the administrators of the server can still use it
+async fn deny(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let name = args.single::<String>()?;
+    let targets = guild_settings::parse_targets(ctx, msg, args.rest()).await?;
+    guild_settings::set_rule(ctx, msg, &name, &targets, Some(false)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_GUILD)]
+#[min_args(1)]
+#[usage = "{name} ?[#channels]"]
+#[example = "game #general"]
+/// Disable a command, a command group or an auto-command in this server
+/// or only in the mentioned channels
+/// A command sharing its name with another one is given with its group, as `reminder.list`
+async fn disable(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let name = args.single::<String>()?;
+    guild_settings::toggle(ctx, msg, &name, Some(false)).await?;
+
+    Ok(())
+}
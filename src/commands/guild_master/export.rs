@@ -0,0 +1,23 @@
+use crate::commands::prelude::*;
+use crate::guild_settings::GuildExport;
+
+#[command]
+#[only_in(guilds)]
+/// Export the options of this server as a JSON file
+/// which can be imported again here or in another server with `option import`
+async fn export(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let export = GuildExport::new(ctx, guild_id).await?;
+    let bytes = serde_json::to_vec_pretty(&export)?;
+    let name = format!("guild_{}.json", guild_id.0);
+
+    msg.channel_id.send_message(ctx, |m| {
+        m.content("Exported successfully!").add_file((bytes.as_slice(), name.as_str()))
+    }).await?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+use crate::types::GuildConfig;
+
+#[command]
+#[only_in(guilds)]
+#[min_args(1)]
+#[usage = "{option}"]
+#[example = "logger.channel"]
+/// Show an option of this server, with its type and default value
+async fn get(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let key = args.single::<String>()?;
+    let setting = match guild_settings::find(&key) {
+        Some(s) => s,
+        None => {
+            let mess = format!("There is no option `{}`, use `option list` to see them all", key);
+            msg.channel_id.say(ctx, mess).await?;
+            return Ok(());
+        }
+    };
+
+    let value = {
+        let config = crate::read_config().await;
+        let guild = config.guilds.get(&guild_id);
+
+        match guild.as_deref() {
+            Some(g) => setting.get(g),
+            None => setting.get(&GuildConfig::default()),
+        }
+    };
+
+    guild_settings::send(ctx, msg, setting, &value).await?;
+    Ok(())
+}
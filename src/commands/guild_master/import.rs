@@ -0,0 +1,73 @@
+use crate::commands::prelude::*;
+use crate::guild_settings::{self, GuildExport};
+use magic::traits::{MagicIter as _, MagicStr as _};
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_GUILD)]
+#[usage = "?[--dry-run] (with the exported file attached)"]
+#[example = "--dry-run"]
+/// Import the options from a file exported by `option export`
+/// The channels and roles of another server are matched by name, those not found are left out
+/// Passing __--dry-run__ to only show what would change
+async fn import(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let dry_run = args.raw().any(|v| v == "--dry-run" || v == "-n");
+    let attachment = match msg.attachments.first() {
+        Some(a) => a,
+        None => {
+            msg.channel_id
+                .say(ctx, "Please attach a file exported by the `option export` command")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let bytes = attachment.download().await?;
+    let export: GuildExport = match serde_json::from_slice(&bytes) {
+        Ok(v) => v,
+        Err(why) => {
+            let mess = format!("This is not an exported file: {}", why);
+            msg.channel_id.say(ctx, mess).await?;
+            return Ok(());
+        }
+    };
+
+    let (imported, missing) = export.remap(ctx, guild_id).await?;
+    let report = guild_settings::import(ctx, msg, guild_id, imported, dry_run).await?;
+
+    let title = if dry_run {
+        "Options import (dry run)"
+    } else {
+        "Options import"
+    };
+
+    let changes = if report.changes.is_empty() {
+        String::from("Nothing to change")
+    } else {
+        report.changes.iter().map(|v| v.to_string()).join('\n')
+    };
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title(title);
+    embed.description(changes.split_at_limit(2000, "\n").next().unwrap_or_default());
+    embed.timestamp(now());
+
+    if !missing.is_empty() {
+        let text = missing.join(", ");
+        embed.field("Not found in this server", text.split_at_limit(1000, ", ").next().unwrap_or_default(), false);
+    }
+
+    if !report.denied.is_empty() {
+        let text = report.denied.iter().map(|v| format!("`{}`", v)).join(", ");
+        embed.field("Left untouched, missing the permissions", text, false);
+    }
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_GUILD)]
+#[min_args(1)]
+#[aliases("unset")]
+#[usage = "{name} ?[#channels]"]
+#[example = "find_sauce #art"]
+/// Remove the toggle of a command, a command group or an auto-command
+/// so it follows the server again, or the global config at the server level
+async fn inherit(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let name = args.single::<String>()?;
+    guild_settings::toggle(ctx, msg, &name, None).await?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::commands::prelude::*;
+use crate::guild_settings::SETTINGS;
+use crate::types::GuildConfig;
+
+#[command]
+#[only_in(guilds)]
+/// List every option of this server with its current value
+async fn list(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let config = crate::read_config().await;
+    let fields = {
+        let default = GuildConfig::default();
+        let guild = config.guilds.get(&guild_id);
+        let guild = guild.as_deref().unwrap_or(&default);
+
+        SETTINGS
+            .iter()
+            .map(|v| (format!("`{}`", v.key), v.get(guild).to_string(), true))
+            .collect::<Vec<_>>()
+    };
+
+    let mut embed = config.theme(msg.guild_id).embed(|c| c.information);
+    drop(config);
+
+    embed.title("Server options");
+    embed.timestamp(now());
+    embed.fields(fields);
+    embed.footer(|f| f.text("Use `option get {option}` to see the details of an option"));
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use crate::commands::prelude::*;
+use crate::types::{Invoker, PermissionRule};
+use std::collections::BTreeMap;
+
+use magic::traits::MagicIter as _;
+
+#[command]
+#[only_in(guilds)]
+#[aliases("permissions")]
+#[usage = "?[name] ?[@user] ?[#channel]"]
+#[example = "nhentai @someone #general"]
+/// List the permission rules of this server
+/// With a command or a group, show the rules which apply to it
+/// and whether you, or the mentioned user, may use it here or in the mentioned channel
+async fn perms(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let config = crate::read_config().await;
+    let mut embed = config.theme(msg.guild_id).embed(|c| c.information);
+    let rules = config
+        .guilds
+        .get(&guild_id)
+        .map(|g| g.permissions.to_owned())
+        .unwrap_or_default();
+    let masters = config.masters.to_owned();
+    drop(config);
+
+    fn show(rules: &[&PermissionRule]) -> String {
+        let allowed = rules.iter().filter(|v| v.allow).map(|v| v.target.to_string()).join(", ");
+        let denied = rules.iter().filter(|v| !v.allow).map(|v| v.target.to_string()).join(", ");
+        let mut text = String::new();
+
+        if !allowed.is_empty() {
+            text.push_str(&format!("Allowed to: {}\n", allowed));
+        }
+
+        if !denied.is_empty() {
+            text.push_str(&format!("Denied to: {}\n", denied));
+        }
+
+        text
+    }
+
+    let name = match args.single::<String>() {
+        Ok(v) if !v.starts_with('<') => Some(v),
+        _ => None,
+    };
+
+    let mut fields = Vec::new();
+    let mut description = None;
+
+    match name {
+        None => {
+            let mut by_name: BTreeMap<&str, Vec<&PermissionRule>> = BTreeMap::new();
+
+            for rule in &rules {
+                by_name.entry(&rule.name).or_default().push(rule);
+            }
+
+            for (name, rules) in by_name {
+                fields.push((format!("`{}`", name), show(&rules), false));
+            }
+
+            if fields.is_empty() {
+                description = Some(String::from(
+                    "There is no rule, every command follows its usual permissions",
+                ));
+            }
+        }
+        Some(name) => {
+            let name = crate::framework::toggle_name(&name).map_err(Error::not_found)?;
+            let path = crate::framework::toggle_path(&name).unwrap_or_else(|| vec![name.to_owned()]);
+            let names = path.iter().map(String::as_str).collect::<Vec<_>>();
+
+            for name in &names {
+                let name = name.to_lowercase();
+                let rules = rules.iter().filter(|v| v.name == name).collect::<Vec<_>>();
+
+                if !rules.is_empty() {
+                    fields.push((format!("`{}`", name), show(&rules), false));
+                }
+            }
+
+            let user = msg.mentions.get(0).unwrap_or(&msg.author);
+            let channel = extract_channel_ids(&msg.content)
+                .into_iter()
+                .next()
+                .unwrap_or(msg.channel_id);
+
+            let member = guild_id.member(ctx, user.id).await?;
+            let roles = member.roles.iter().map(|v| v.0).collect::<Vec<_>>();
+            let invoker = Invoker {
+                user: user.id.0,
+                roles: &roles,
+                channel: channel.0,
+            };
+
+            let exempt = masters.contains(&user.id)
+                || member.permissions(ctx).await.map_or(false, |v| v.administrator());
+
+            let (enabled, rule) = match crate::read_config().await.guilds.get(&guild_id) {
+                Some(g) => (
+                    g.is_enabled(channel.0, &names, true),
+                    g.permission(&names, &invoker).cloned(),
+                ),
+                None => (true, None),
+            };
+
+            let verdict = match rule {
+                _ if !enabled => format!("`{}` is disabled in <#{}>", name, channel.0),
+                _ if exempt => format!(
+                    "{} can use `{}`, the rules do not apply to them",
+                    user.name, name
+                ),
+                Some(rule) if rule.allow => format!(
+                    "{} can use `{}` in <#{}>, allowed to {} on `{}`",
+                    user.name, name, channel.0, rule.target, rule.name
+                ),
+                Some(rule) => format!(
+                    "{} cannot use `{}` in <#{}>, denied to {} on `{}`",
+                    user.name, name, channel.0, rule.target, rule.name
+                ),
+                None => format!(
+                    "No rule applies to {} for `{}` in <#{}>, the usual permissions decide",
+                    user.name, name, channel.0
+                ),
+            };
+
+            description = Some(verdict);
+        }
+    }
+
+    embed.title("Permission rules");
+    embed.timestamp(now());
+
+    if let Some(description) = description {
+        embed.description(description);
+    }
+
+    embed.fields(fields);
+    embed.footer(|f| f.text(
+        "The first name with a rule wins, then the users, the roles, the channels and everyone",
+    ));
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
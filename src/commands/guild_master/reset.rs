@@ -0,0 +1,31 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[min_args(1)]
+#[usage = "{option}"]
+#[example = "prefix"]
+/// Change an option of this server back to its default value
+async fn reset(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let key = args.single::<String>()?;
+    let setting = match guild_settings::find_writable(ctx, msg, &key).await? {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let default = setting.default_value();
+    let value = guild_settings::update(ctx, guild_id, setting, move |v| {
+        *v = default.clone()
+    })
+    .await?;
+
+    guild_settings::send(ctx, msg, setting, &value).await?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[min_args(2)]
+#[usage = "{option} {value}"]
+#[example = "logger.channel #logs"]
+/// Change an option of this server
+/// Use `option list` to see every option, and `option get {option}` for its type
+async fn set(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let key = args.single::<String>()?;
+    let setting = match guild_settings::find_writable(ctx, msg, &key).await? {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    let new = match setting.kind.parse(ctx, msg, args.rest()).await {
+        Ok(v) => v,
+        Err(why) => {
+            msg.channel_id.say(ctx, why).await?;
+            return Ok(());
+        }
+    };
+
+    let value = guild_settings::update(ctx, guild_id, setting, move |v| *v = new.clone()).await?;
+    guild_settings::send(ctx, msg, setting, &value).await?;
+
+    Ok(())
+}
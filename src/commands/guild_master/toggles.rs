@@ -0,0 +1,83 @@
+use crate::commands::prelude::*;
+use crate::types::Toggles;
+use serenity::model::id::ChannelId;
+
+#[command]
+#[only_in(guilds)]
+/// List the commands, command groups and auto-commands toggled in this server and its channels
+async fn toggles(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    fn show(toggles: &Toggles) -> String {
+        let mut enabled = toggles.enabled.iter().map(|v| format!("`{}`", v)).collect::<Vec<_>>();
+        let mut disabled = toggles.disabled.iter().map(|v| format!("`{}`", v)).collect::<Vec<_>>();
+        enabled.sort();
+        disabled.sort();
+
+        let mut text = String::new();
+
+        if !enabled.is_empty() {
+            text.push_str(&format!("Enabled: {}\n", enabled.join(", ")));
+        }
+
+        if !disabled.is_empty() {
+            text.push_str(&format!("Disabled: {}\n", disabled.join(", ")));
+        }
+
+        text
+    }
+
+    let config = crate::read_config().await;
+    let levels = match config.guilds.get(&guild_id) {
+        Some(guild) => {
+            let mut channels = guild
+                .channel_toggles
+                .iter()
+                .map(|(id, toggles)| (Some(*id), show(toggles)))
+                .collect::<Vec<_>>();
+
+            channels.sort();
+
+            Some((None, show(&guild.toggles)))
+                .into_iter()
+                .chain(channels)
+                .filter(|(_, text)| !text.is_empty())
+                .collect::<Vec<_>>()
+        }
+        None => Vec::new(),
+    };
+
+    let mut embed = config.theme(msg.guild_id).embed(|c| c.information);
+    drop(config);
+
+    let mut fields = Vec::with_capacity(levels.len());
+
+    for (channel, text) in levels {
+        let name = match channel {
+            Some(id) => match ChannelId(id).name(ctx).await {
+                Some(name) => format!("#{}", name),
+                None => id.to_string(),
+            },
+            None => String::from("Server"),
+        };
+
+        fields.push((name, text, false));
+    }
+
+    embed.title("Command toggles");
+    embed.timestamp(now());
+
+    if fields.is_empty() {
+        embed.description("Nothing is toggled, every command follows the global config");
+    } else {
+        embed.fields(fields);
+    }
+
+    embed.footer(|f| f.text("The channel toggles take precedence over the server ones"));
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
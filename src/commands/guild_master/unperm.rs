@@ -0,0 +1,19 @@
+use crate::commands::prelude::*;
+use crate::guild_settings;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_GUILD)]
+#[min_args(2)]
+#[aliases("unallow", "undeny")]
+#[usage = "{name} {@users|@roles|#channels|everyone}"]
+#[example = "nhentai @Adults"]
+/// Remove the permission rules of a command or a command group
+/// for the mentioned users, roles or channels
+async fn unperm(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let name = args.single::<String>()?;
+    let targets = guild_settings::parse_targets(ctx, msg, args.rest()).await?;
+    guild_settings::set_rule(ctx, msg, &name, &targets, None).await?;
+
+    Ok(())
+}
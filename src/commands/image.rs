@@ -1,9 +1,11 @@
+use crate::slash::SlashCommand;
 use crate::utils::get_file_bytes;
 use bytes::Bytes;
 use magic::import_all;
 use serenity::client::Context;
 use serenity::framework::standard::macros::group;
 use serenity::model::channel::Message;
+use serenity::model::id::ChannelId;
 
 import_all! {
     rotate,
@@ -16,6 +18,8 @@ import_all! {
 #[commands(rotate, flip, saucenao, diancie)]
 struct Image;
 
+pub static IMAGE_SLASH_COMMANDS: &[&SlashCommand] = &[&SAUCENAO_SLASH];
+
 /// Get the last image buf from most recent message on the channel
 /// Max messages length is 100
 pub async fn get_last_image_buf(ctx: &Context, msg: &Message, limit: u16) -> Option<Bytes> {
@@ -35,6 +39,16 @@ pub async fn get_last_image_url(ctx: &Context, msg: &Message, limit: u16) -> Opt
     }
 }
 
+/// The same as `get_last_image_url`, without a message to start from
+pub async fn get_recent_image_url(ctx: &Context, channel: ChannelId, limit: u16) -> Option<String> {
+    channel
+        .messages(ctx, |m| m.limit(limit as u64))
+        .await
+        .ok()?
+        .into_iter()
+        .find_map(|v| get_image_url_from_message(&v))
+}
+
 #[inline]
 fn get_image_url_from_message(msg: &Message) -> Option<String> {
     msg.attachments
@@ -1,22 +1,55 @@
-use super::get_last_image_url;
+use super::{get_last_image_url, get_recent_image_url};
 use crate::commands::prelude::*;
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
 use crate::traits::Embedable as _;
+use futures::future::FutureExt as _;
 use requester::SauceNaoScraper as _;
+use serenity::builder::CreateEmbed;
 use crate::types::Ref;
 
+pub static SAUCENAO_SLASH: SlashCommand = SlashCommand {
+    command: &SAUCENAO_COMMAND,
+    name: None,
+    options: &[
+        SlashOption {
+            name: "url",
+            description: "The last image of the channel if not given",
+            kind: OptionKind::String,
+            required: false,
+        },
+        SlashOption {
+            name: "similarity",
+            description: "The minimum similarity in percent",
+            kind: OptionKind::Integer,
+            required: false,
+        },
+    ],
+    run: |ctx, origin, args| {
+        let url = args.string("url").map(String::from);
+        let similarity = args.integer("similarity").map(|v| v as f32);
+
+        async move {
+            let img = match url {
+                Some(v) => Some(v),
+                None => {
+                    let depth = crate::read_config().await.image_search_depth;
+                    get_recent_image_url(ctx, origin.channel_id(), depth).await
+                }
+            };
+
+            find_sauce(ctx, origin, img, similarity).await
+        }
+        .boxed()
+    },
+};
+
 #[command]
 #[aliases("sauce")]
+#[bucket = "expensive"]
 /// Find an anime image source.
 async fn saucenao(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let depth = crate::read_config().await.image_search_depth;
-    let img = match get_last_image_url(&ctx, &msg, depth).await {
-        Some(i) => i,
-        None => {
-            let to_say = format!("Cannot find an image from last {} message", depth);
-            msg.channel_id.say(ctx, to_say).await?;
-            return Ok(());
-        }
-    };
+    let img = get_last_image_url(&ctx, &msg, depth).await;
 
     let similarity = args.raw().find_map(|v| {
         if v.ends_with('%') {
@@ -26,6 +59,25 @@ async fn saucenao(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         }
     });
 
+    find_sauce(ctx, &Origin::from(msg), img, similarity).await
+}
+
+async fn find_sauce(
+    ctx: &Context,
+    origin: &Origin<'_>,
+    img: Option<String>,
+    similarity: Option<f32>,
+) -> CommandResult {
+    let img = match img {
+        Some(i) => i,
+        None => {
+            let depth = crate::read_config().await.image_search_depth;
+            let to_say = format!("Cannot find an image from last {} message", depth);
+            origin.say(ctx, to_say).await?;
+            return Ok(());
+        }
+    };
+
     let data = get_data::<ReqwestClient>(&ctx)
         .await
         .unwrap()
@@ -34,11 +86,13 @@ async fn saucenao(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         .map(Ref::from)?;
 
     if data.not_found() {
-        msg.channel_id.say(ctx, "Error 404: No sauce found").await?;
+        origin.say(ctx, "Error 404: No sauce found").await?;
         return Ok(());
     }
     
-    msg.channel_id.send_message(ctx, |m| m.embed(|embed| data.append_to(embed))).await?;
+    let mut embed = CreateEmbed::default();
+    data.append_to(&mut embed);
+    origin.send_embed(ctx, embed).await?;
 
     Ok(())
 }
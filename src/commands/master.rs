@@ -7,6 +7,9 @@ import_all! {
     clear_cache,
     system_info,
     save_config,
+    export_db,
+    import_db,
+    snapshot,
     reload,
     restart,
     shutdown
@@ -20,6 +23,9 @@ import_all! {
     clear_cache,
     system_info,
     save_config,
+    export_db,
+    import_db,
+    snapshot,
     reload,
     restart,
     shutdown
@@ -10,19 +10,12 @@ async fn clear_cache(ctx: &Context, msg: &Message) -> CommandResult {
     let cache = get_data::<CacheStorage>(&ctx).await.unwrap();
     let (length, size) = cache.clear().await?;
     
-    let color = {
-        let config = crate::read_config().await;
-        config.color.information
-    };
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.description("Cleared the custom cache");
+    embed.field("Message cached", length, true);
+    embed.field("Temp files", report_bytes(size as _), true);
 
-    msg.channel_id.send_message(&ctx.http, move |m| m.embed(|embed| {
-        embed.description("Cleared the custom cache");
-        embed.field("Message cached", length, true);
-        embed.field("Temp files", report_bytes(size as _), true);
-        embed.color(color);
-        
-        embed
-    })).await?;
+    msg.channel_id.send_message(&ctx.http, |m| m.set_embed(embed)).await?;
 
     Ok(())
 }
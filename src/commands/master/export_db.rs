@@ -0,0 +1,53 @@
+use crate::commands::prelude::*;
+use db::Archive;
+
+#[command]
+#[aliases("exportdb", "backup")]
+#[owners_only]
+#[usage = "?[tree names]"]
+#[example = "GuildConfig Reminders"]
+/// Export the database to a JSON file
+/// Passing the tree names to export only these trees, otherwise every tree will be exported
+async fn export_db(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let trees: Vec<String> = args.raw().map(String::from).collect();
+    let db = get_db(ctx).await?;
+
+    let dir = crate::read_config()
+        .await
+        .temp_dir
+        .to_owned()
+        .unwrap_or_else(|| ".".into());
+
+    let (archive, path) = tokio::task::spawn_blocking(move || -> crate::Result<_> {
+        let archive = Archive::export(&db, &crate::schemas(), |name| {
+            trees.is_empty() || trees.iter().any(|v| v == name)
+        })?;
+
+        // Named like the snapshots, never over an export made in the same second
+        let (path, _) = crate::snapshot::reserve_path(&dir)?;
+        Ok((archive, path))
+    })
+    .await??;
+
+    let data = serde_json::to_vec_pretty(&archive)?;
+    fs::write(&path, data).await?;
+
+    let mut content = format!(
+        "Exported {} records from {} trees",
+        archive.len(),
+        archive.trees.len()
+    );
+
+    if archive.skipped() > 0 {
+        content.push_str(&format!(
+            "\n{} records cannot be read and were left out, see the logs",
+            archive.skipped()
+        ));
+    }
+
+    msg.channel_id
+        .send_message(ctx, |m| m.content(content).add_file(&path))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::commands::prelude::*;
+use db::Archive;
+use magic::traits::MagicStr as _;
+
+#[command]
+#[aliases("importdb", "restore")]
+#[owners_only]
+#[usage = "?[--dry-run] (with the exported file attached)"]
+#[example = "--dry-run"]
+/// Restore the database from a file exported by the `export_db` command
+/// Every tree in the file replaces the current one, the others are left untouched
+/// Passing __--dry-run__ to only show what would change
+async fn import_db(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let dry_run = args.raw().any(|v| v == "--dry-run" || v == "-n");
+    let attachment = match msg.attachments.first() {
+        Some(a) => a,
+        None => {
+            msg.channel_id
+                .say(ctx, "Please attach a file exported by the `export_db` command")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let bytes = attachment.download().await?;
+    let archive: Archive = serde_json::from_slice(&bytes)?;
+    let db = get_db(ctx).await?;
+
+    let report = tokio::task::spawn_blocking(move || {
+        archive.import(&db, &crate::schemas(), dry_run)
+    })
+    .await??;
+
+    let title = if dry_run {
+        "Database import (dry run)"
+    } else {
+        "Database import"
+    };
+
+    let text = report.to_string();
+    let description = text.split_at_limit(2000, "\n").next().unwrap_or_default();
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title(title);
+    embed.description(format!("```{}```", description));
+    embed.timestamp(now());
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,17 +1,29 @@
 use crate::commands::prelude::*;
+use magic::traits::{MagicIter as _, MagicStr as _};
 
 #[command]
 #[owners_only]
-/// Reload the `config.toml`
+/// Reload the `config.toml`, it is also reloaded automatically whenever the file is modified
 async fn reload(ctx: &Context, msg: &Message) -> CommandResult {
-    let db = get_data::<DatabaseKey>(&ctx).await.unwrap();
-    let mut config = crate::write_config().await;
-    
-    config.reload(&db)?;
-    
-    drop(db);
-    drop(config);
-    
-    msg.channel_id.say(ctx, "Reloaded the config").await?;
+    let changes = match crate::config_watcher::reload().await {
+        Ok(v) => v,
+        Err(why) => {
+            msg.channel_id.say(ctx, format!("The config is invalid, nothing has been reloaded\n{}", why)).await?;
+            return Ok(());
+        }
+    };
+
+    if changes.is_empty() {
+        msg.channel_id.say(ctx, "Reloaded the config, nothing has changed").await?;
+        return Ok(());
+    }
+
+    let text = changes.iter().map(|v| v.to_string()).join('\n');
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title("Reloaded the config");
+    embed.description(text.split_at_limit(2000, "\n").next().unwrap_or_default());
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -2,22 +2,41 @@ use crate::commands::prelude::*;
 
 #[command]
 #[owners_only]
-/// Save config to a file
-async fn save_config(ctx: &Context, msg: &Message) -> CommandResult {
+#[usage = "?[json] ?[--secrets]"]
+#[example = "json --secrets"]
+/// Save config to a TOML file, or a JSON one with __json__
+/// The API keys and cookies are redacted, passing __--secrets__ to keep them
+/// the file is then sent in DM instead of in the current channel
+async fn save_config(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let json = args.raw().any(|v| v.eq_ignore_ascii_case("json"));
+    let show_secrets = args.raw().any(|v| v == "--secrets");
+
     let config = crate::read_config().await;
-    
-    let path = config
+
+    let mut path = config
         .temp_dir
         .to_owned()
         .unwrap_or_else(|| ".".into());
-        
-    let file = config.save_file(path).await?;
-    
+
+    if json {
+        path.push(format!("config_{}.json", chrono::Utc::now().timestamp_millis()));
+    }
+
+    let file = config.save_file(path, show_secrets).await?;
+
     drop(config);
-    
-    msg.channel_id.send_message(ctx, |m| {
-        m.content("Saved successfully!").add_file(&file)
-    }).await?;
-    
+
+    if show_secrets {
+        msg.author.direct_message(ctx, |m| {
+            m.content("Saved successfully, this file contains the secrets!").add_file(&file)
+        }).await?;
+
+        msg.channel_id.say(ctx, "Saved successfully, I've sent the file in DM").await?;
+    } else {
+        msg.channel_id.send_message(ctx, |m| {
+            m.content("Saved successfully!").add_file(&file)
+        }).await?;
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
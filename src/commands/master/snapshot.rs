@@ -0,0 +1,110 @@
+use crate::commands::prelude::*;
+use crate::snapshot::{self, SnapshotFile};
+use chrono::{TimeZone, Utc};
+use magic::traits::MagicIter as _;
+
+#[command]
+#[aliases("snapshots")]
+#[owners_only]
+#[usage = "?[list | now | restore {index} | cancel]"]
+#[example = "restore 2"]
+/// Manage the snapshots of the database, listing them by default
+/// __now__ to take a snapshot right away
+/// __restore {index}__ to restore a snapshot on the next startup, use `restart` after it
+/// __cancel__ to cancel the pending restore
+async fn snapshot(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let (dir, keep) = {
+        let config = crate::read_config().await;
+        (config.snapshot.dir.to_owned(), config.snapshot.keep)
+    };
+
+    let action = args.single::<String>().unwrap_or_else(|_| "list".to_string());
+
+    match action.to_lowercase().as_str() {
+        "now" | "take" => {
+            let db = get_db(ctx).await?;
+
+            let file = snapshot::take(&db, &dir, keep).await?;
+            let content = format!("Saved the snapshot **{}** ({} KB)", file.name(), file.size / 1024);
+            msg.channel_id.say(ctx, content).await?;
+        }
+
+        "restore" => {
+            let index = match args.single::<usize>() {
+                Ok(i) if i > 0 => i,
+                _ => {
+                    msg.channel_id.say(ctx, "Please give me the index of the snapshot to restore").await?;
+                    return Ok(());
+                }
+            };
+
+            let file = match snapshot::list(&dir).await?.into_iter().nth(index - 1) {
+                Some(f) => f,
+                None => {
+                    msg.channel_id.say(ctx, format!("There is no snapshot at index {}", index)).await?;
+                    return Ok(());
+                }
+            };
+
+            snapshot::schedule_restore(&dir, &file).await?;
+
+            let content = format!(
+                "**{}** will be restored on the next startup, the current data will be saved to a new snapshot first",
+                file.name()
+            );
+
+            msg.channel_id.say(ctx, content).await?;
+        }
+
+        "cancel" => {
+            let content = if snapshot::cancel_restore(&dir).await? {
+                "Cancelled the pending restore"
+            } else {
+                "There is no pending restore"
+            };
+
+            msg.channel_id.say(ctx, content).await?;
+        }
+
+        "list" => {
+            let files = snapshot::list(&dir).await?;
+            let pending = snapshot::pending_restore(&dir).await;
+
+            let text = files
+                .iter()
+                .zip(1..)
+                .map(|(file, i)| describe(i, file, pending.as_deref()))
+                .join('\n');
+
+            let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+            embed.title(format!("Snapshots in {}", dir.display()));
+            embed.description(if text.is_empty() {
+                "There is no snapshot yet"
+            } else {
+                text.as_str()
+            });
+
+            embed.footer(|f| f.text(format!("Keeping the last {} snapshots", keep)));
+            msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+        }
+
+        _ => {
+            msg.channel_id.say(ctx, "Unknown action, please use one of `list`, `now`, `restore` or `cancel`").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn describe(index: usize, file: &SnapshotFile, pending: Option<&str>) -> String {
+    let date = Utc.timestamp_millis(file.created_at as i64).format("%F %T UTC");
+    let name = file.name();
+
+    let mut res = format!("**{}.** *{}* __{}__ ({} KB)", index, date, name, file.size / 1024);
+
+    if pending == Some(name.as_str()) {
+        res.push_str(" - **restoring on the next startup**");
+    }
+
+    res
+}
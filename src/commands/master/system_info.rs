@@ -34,19 +34,11 @@ async fn system_info(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
         fields.extend(addition);
     }
     
-    let config = crate::read_config().await;
-    let color = config.color.information;
-    drop(config);
-
-    msg.channel_id.send_message(&ctx.http, |m| {
-        m.embed(|embed| {
-            embed.title("System Information");
-            embed.color(color);
-            embed.fields(fields);
-            
-            embed
-        })
-    }).await?;
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title("System Information");
+    embed.fields(fields);
+
+    msg.channel_id.send_message(&ctx.http, |m| m.set_embed(embed)).await?;
 
     Ok(())
 }
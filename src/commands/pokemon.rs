@@ -1,12 +1,13 @@
 use crate::commands::prelude::*;
 use crate::config::PokemonEmoji;
 use crate::constants::*;
-use crate::traits::{ChannelExt, Embedable, Paginator};
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
+use crate::traits::{Embedable, Paginator};
 use crate::types::Ref;
 use crate::Result;
 use core::time::Duration;
 use db::DbInstance;
-use futures::future;
+use futures::future::{self, FutureExt as _};
 use magic::import_all;
 use magic::traits::MagicIter;
 use pokemon_core::types::Type;
@@ -28,6 +29,9 @@ import_all! {
     r#move
 }
 
+/// Smogon rarely changes its analyses, a week is fresh enough
+const DESCRIPTION_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 const POKEMON_VERSIONS: [Generation; 8] = [
     Generation::RedBlue,
     Generation::GoldSilver,
@@ -70,93 +74,106 @@ impl PokeKey {
     }
 }
 
+pub static POKEMON_SLASH: SlashCommand = SlashCommand {
+    command: &POKEMON_COMMAND,
+    name: None,
+    options: &[SlashOption {
+        name: "query",
+        description: "A pokemon, an ability, a move, an item, a nature or types, then a generation",
+        kind: OptionKind::String,
+        required: true,
+    }],
+    run: |ctx, origin, args| lookup(ctx, origin, args.string("query").unwrap_or_default()).boxed(),
+};
+
+pub static POKEMON_SLASH_COMMANDS: &[&SlashCommand] = &[&POKEMON_SLASH];
+
 #[command]
 #[min_args(1)]
+/// Look up a pokemon, an ability, a move, an item, a nature or the effectiveness of types
 async fn pokemon(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-    let args = args.rest();
+    lookup(ctx, &Origin::from(msg), args.rest()).await
+}
+
+async fn lookup(ctx: &Context, origin: &Origin<'_>, args: &str) -> CommandResult {
     let (text, gen) = match parse_args(&args) {
         Some(v) => v,
         None => return Ok(()),
     };
 
-    let db = match get_data::<DatabaseKey>(ctx).await {
-        Some(db) => db,
-        None => return Err("Cannot get the database".into()),
-    };
-
-    let name = text.to_lowercase().replace(' ', "-");
+    let db = get_db(ctx).await?;
 
     let db_data = tokio::task::spawn_blocking(move || {
         let database = db.open(SMOGON_POKEMON).ok()?;
-
-        database
-            .get_all_keys::<PokeKey>()
-            .find(|v| &v.name == &name && gen == v.gen)
-            .map(|k| (k, database))
+        find_key(&database, &text, gen).map(|k| (k, database))
     })
     .await?;
 
     if let Some((key, db)) = db_data {
-        let processed = process_data(ctx, key, msg, Some(db)).await?;
+        let processed = process_data(ctx, key, origin, Some(db)).await?;
 
         if processed {
             return Ok(());
         }
     }
 
-    if process_nature(ctx, msg, &args).await? {
+    if process_nature(ctx, origin, &args).await? {
         return Ok(());
     }
 
-    if process_types(ctx, msg, &args).await? {
+    if process_types(ctx, origin, &args).await? {
         return Ok(());
     }
 
-    Err(format!("Cannot not find the `{}` in my almighty database", args).into())
+    Err(Error::not_found(format!("Cannot find `{}` in my almighty database", args)).into())
+}
+
+/// The first thing named `name` in the generation `gen`, whatever its kind
+fn find_key(pokemon: &DbInstance, name: &str, gen: Generation) -> Option<PokeKey> {
+    let name = name.to_lowercase().replace(' ', "-");
+
+    // The key starts with the name then the generation
+    pokemon
+        .scan_prefix::<_, PokeKey, ()>(&(&name, gen))
+        .ok()?
+        .keys()
+        .next()
 }
 
 async fn process_data(
     ctx: &Context,
     key: PokeKey,
-    msg: &Message,
+    origin: &Origin<'_>,
     db: Option<DbInstance>,
 ) -> Result<bool> {
     let db = match db {
         Some(d) => d,
-        None => get_data::<DatabaseKey>(ctx)
-            .await
-            .expect("Database not found")
-            .open(SMOGON_POKEMON)?,
+        None => get_db(ctx).await?.open(SMOGON_POKEMON)?,
     };
 
     macro_rules! get_info {
         ($x:ident, $dump:ident) => {{
-            let info: $x = match db.get(&key)? {
+            let info: $x = match db.get_async(&key).await? {
                 Some(d) => d,
                 None => return Ok(false),
             };
 
-            let desc_db = db.open(SMOGON_DESCRIPTION)?;
-            let mut desc: Option<SmogonCommon> = desc_db.get(&key)?;
+            let desc: SmogonCommon = db
+                .open(SMOGON_DESCRIPTION)?
+                .get_or_fetch(&key, DESCRIPTION_TTL, || async {
+                    let data = get_data::<ReqwestClient>(ctx)
+                        .await
+                        .expect("Http Requester")
+                        .$dump(&info.name, key.gen)
+                        .await?;
 
-            if desc.is_none() {
-                let data = get_data::<ReqwestClient>(ctx)
-                    .await
-                    .expect("Http Requester")
-                    .$dump(&info.name, key.gen)
-                    .await?;
+                    Ok(data)
+                })
+                .await?;
 
-                desc_db.insert(&key, &data)?;
-                desc = Some(data);
-            }
-
-            let desc = desc.unwrap();
-
-            let mut send_embed = msg
-                .channel_id
-                .send_embed(ctx)
-                .with_embedable_object(Ref(info))
-                .with_description(desc.description);
+            let mut embed = CreateEmbed::default();
+            Ref(info).append_to(&mut embed);
+            embed.description(desc.description);
 
             if let Some(pokemon) = desc.pokemon.filter(|v| !v.is_empty()) {
                 let pokemons = if pokemon.len() > 50 {
@@ -175,15 +192,15 @@ async fn process_data(
                     pokemon.join(", ")
                 };
 
-                send_embed.field("Pokemons", pokemons, false);
+                embed.field("Pokemons", pokemons, false);
             }
 
-            send_embed.await?;
+            origin.send_embed(ctx, embed).await?;
         }};
     }
 
     match key.kind {
-        PokeKeyKind::Pokemon => process_pokemon_data(ctx, msg, key, db).await?,
+        PokeKeyKind::Pokemon => process_pokemon_data(ctx, origin, key, db).await?,
         PokeKeyKind::Item => get_info!(SmogonItem, dump_item),
         PokeKeyKind::Move => get_info!(SmogonMove, dump_move),
         PokeKeyKind::Ability => get_info!(SmogonAbility, dump_ability),
@@ -194,11 +211,11 @@ async fn process_data(
 
 pub async fn process_pokemon_data(
     ctx: &Context,
-    msg: &Message,
+    origin: &Origin<'_>,
     key: PokeKey,
     db: DbInstance,
 ) -> Result<()> {
-    let info: SmogonPokemon = match db.get(&key)? {
+    let info: SmogonPokemon = match db.get_async(&key).await? {
         Some(d) => d,
         None => return Ok(()),
     };
@@ -273,31 +290,30 @@ pub async fn process_pokemon_data(
         key.gen
     };
 
-    let mut send_embed = msg
-        .channel_id
-        .send_embed(ctx)
-        .with_title(title)
-        .with_thumbnail(sprite)
-        .with_field("Type Advantages", types, true)
-        .with_field(base_title, base_stats, false)
-        .with_field("Abilities", abilities, false)
-        .with_footer_text(format!("Generation: {}", gen));
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(title)
+        .thumbnail(sprite)
+        .field("Type Advantages", types, true)
+        .field(base_title, base_stats, false)
+        .field("Abilities", abilities, false)
+        .footer(|f| f.text(format!("Generation: {}", gen)));
 
     if not_in_swsh {
-        send_embed.description("This pokemon isn't available in sword/shield yet...");
+        embed.description("This pokemon isn't available in sword/shield yet...");
     }
 
     if let Some(oob) = info.oob.as_ref() {
         if !oob.evos.is_empty() {
-            send_embed.field("Next Evolution", oob.evos.join("\n"), true);
+            embed.field("Next Evolution", oob.evos.join("\n"), true);
         }
 
         if !oob.alts.is_empty() {
-            send_embed.field("Altenative Pokemon", oob.alts.join("\n"), true);
+            embed.field("Altenative Pokemon", oob.alts.join("\n"), true);
         }
     }
 
-    let message = send_embed.await?;
+    let message = origin.send_embed(ctx, embed).await?;
     let reaction = ReactionType::Unicode(String::from("⚔"));
     let duration = Duration::from_secs(30);
     let reacted = wait_for_reaction(ctx, &message, reaction, duration).await?;
@@ -306,7 +322,7 @@ pub async fn process_pokemon_data(
         use crate::traits::paginator::PaginatorOption;
 
         let opt = PaginatorOption {
-            channel_id: msg.channel_id,
+            channel_id: origin.channel_id(),
             user,
         };
 
@@ -319,7 +335,7 @@ pub async fn process_pokemon_data(
     Ok(())
 }
 
-async fn process_nature(ctx: &Context, msg: &Message, args: &str) -> Result<bool> {
+async fn process_nature(ctx: &Context, origin: &Origin<'_>, args: &str) -> Result<bool> {
     let filter = nature::Filter::from(args);
 
     if filter.is_empty() {
@@ -336,10 +352,9 @@ async fn process_nature(ctx: &Context, msg: &Message, args: &str) -> Result<bool
         data = format!("Cannot find any nature with `{}`", args);
     }
 
-    msg.channel_id
-        .send_embed(ctx)
-        .with_description(data)
-        .await?;
+    let mut embed = CreateEmbed::default();
+    embed.description(data);
+    origin.send_embed(ctx, embed).await?;
 
     Ok(true)
 }
@@ -351,7 +366,7 @@ fn styled_type(t: Type, emoji: Option<&PokemonEmoji>) -> String {
     }
 }
 
-async fn process_types(ctx: &Context, msg: &Message, args: &str) -> Result<bool> {
+async fn process_types(ctx: &Context, origin: &Origin<'_>, args: &str) -> Result<bool> {
     let mut types = Vec::new();
 
     for s in args.split_whitespace() {
@@ -369,7 +384,22 @@ async fn process_types(ctx: &Context, msg: &Message, args: &str) -> Result<bool>
 
     let types_paginator = TypePagination { types, emoji };
 
-    types_paginator.pagination(ctx, msg).await?;
+    match origin.message() {
+        Some(msg) => types_paginator.pagination(ctx, msg).await?,
+        None => {
+            use crate::traits::paginator::PaginatorOption;
+
+            origin.hand_over(ctx).await?;
+
+            let opt = PaginatorOption {
+                channel_id: origin.channel_id(),
+                user: origin.author().id,
+            };
+
+            types_paginator.pagination(ctx, opt).await?;
+        }
+    }
+
     Ok(true)
 }
 
@@ -449,30 +479,27 @@ pub async fn update_pokemon<R: Requester>(db: &DbInstance, req: &R) -> Result<()
     let data = future::try_join_all(requests).await?;
     let pokemon = db.open(SMOGON_POKEMON)?;
 
-    tokio::task::spawn_blocking(move || {
-        for (data, gen) in data.into_iter().zip(&POKEMON_VERSIONS) {
-            let mut batch = db::Batch::new();
+    for (data, gen) in data.into_iter().zip(&POKEMON_VERSIONS) {
+        let mut batch = db::Batch::new();
 
-            macro_rules! insert {
-                ($x:ident, $t:expr) => {
-                    for v in data.$x {
-                        let key = PokeKey::new(&v.name, *gen, $t);
-                        batch.insert(&key, &v)?;
-                    }
-                };
+        macro_rules! insert {
+            ($x:ident, $t:expr) => {
+                for v in data.$x {
+                    let key = PokeKey::new(&v.name, *gen, $t);
+                    batch.insert(&key, &v)?;
+                }
             };
+        };
 
-            insert!(pokemon, PokeKeyKind::Pokemon);
-            insert!(abilities, PokeKeyKind::Ability);
-            insert!(moves, PokeKeyKind::Move);
-            insert!(items, PokeKeyKind::Item);
+        insert!(pokemon, PokeKeyKind::Pokemon);
+        insert!(abilities, PokeKeyKind::Ability);
+        insert!(moves, PokeKeyKind::Move);
+        insert!(items, PokeKeyKind::Item);
 
-            pokemon.batch(batch)?;
-        }
+        pokemon.batch_async(batch).await?;
+    }
 
-        Ok(())
-    })
-    .await?
+    Ok(())
 }
 
 impl Embedable for Ref<SmogonMove> {
@@ -522,21 +549,19 @@ impl MovesPaginator {
     ) -> Result<Self> {
         let key = PokeKey::new(pokemon, gen, PokeKeyKind::Pokemon);
 
-        let desc_db = db.open(SMOGON_DESCRIPTION)?;
-
-        let learnset = match desc_db.get::<PokeKey, SmogonPokemonDump>(&key)? {
-            Some(pokemon) => pokemon.learnset,
-            None => {
-                let data = get_data::<ReqwestClient>(ctx)
+        let learnset = db
+            .open(SMOGON_DESCRIPTION)?
+            .get_or_fetch(&key, DESCRIPTION_TTL, || async {
+                let data: SmogonPokemonDump = get_data::<ReqwestClient>(ctx)
                     .await
                     .unwrap()
                     .dump_pokemon(&key.name, gen)
                     .await?;
 
-                desc_db.insert(&key, &data)?;
-                data.learnset
-            }
-        };
+                Ok(data)
+            })
+            .await?
+            .learnset;
 
         let icons = crate::read_config().await.emoji.pokemon.to_owned();
         let list: Vec<_> = learnset
@@ -681,3 +706,60 @@ impl Paginator for TypePagination {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemon_db() -> DbInstance {
+        let pokemon = DbInstance::memory().open(SMOGON_POKEMON).unwrap();
+        let mut batch = db::Batch::new();
+
+        let keys = [
+            ("Mew", Generation::SunMoon, PokeKeyKind::Pokemon),
+            ("Mewtwo", Generation::SunMoon, PokeKeyKind::Pokemon),
+            ("Tapu Koko", Generation::SunMoon, PokeKeyKind::Pokemon),
+            ("Leftovers", Generation::SunMoon, PokeKeyKind::Item),
+            ("Dragapult", Generation::SwordShield, PokeKeyKind::Pokemon),
+        ];
+
+        for (name, gen, kind) in keys.iter().cloned() {
+            batch.insert(&PokeKey::new(name, gen, kind), &()).unwrap();
+        }
+
+        pokemon.batch(batch).unwrap();
+        pokemon
+    }
+
+    #[test]
+    fn find_the_exact_name() {
+        let pokemon = pokemon_db();
+
+        let key = find_key(&pokemon, "Mew", Generation::SunMoon).unwrap();
+        assert_eq!(key.name, "mew");
+        assert!(matches!(key.kind, PokeKeyKind::Pokemon));
+
+        let key = find_key(&pokemon, "mewtwo", Generation::SunMoon).unwrap();
+        assert_eq!(key.name, "mewtwo");
+    }
+
+    #[test]
+    fn find_any_kind_by_its_name() {
+        let pokemon = pokemon_db();
+
+        let key = find_key(&pokemon, "Tapu Koko", Generation::SunMoon).unwrap();
+        assert_eq!(key.name, "tapu-koko");
+
+        let key = find_key(&pokemon, "leftovers", Generation::SunMoon).unwrap();
+        assert!(matches!(key.kind, PokeKeyKind::Item));
+    }
+
+    #[test]
+    fn find_nothing_in_another_generation() {
+        let pokemon = pokemon_db();
+
+        assert!(find_key(&pokemon, "Dragapult", Generation::SunMoon).is_none());
+        assert!(find_key(&pokemon, "Dragapult", Generation::SwordShield).is_some());
+        assert!(find_key(&pokemon, "Me", Generation::SunMoon).is_none());
+    }
+}
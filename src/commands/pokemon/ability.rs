@@ -3,10 +3,11 @@ use super::{PokeKey, PokeKeyKind, process_data, parse_args};
 
 
 #[command]
+#[bucket = "expensive"]
 async fn ability(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let (ability, gen) = match parse_args(args.rest()) {
         Some(v) => v,
-        None => return Err("Please specify a ability".into())
+        None => return Err(Error::user("Please specify an ability").into())
     };
     
     let key = PokeKey::new(&ability, gen, PokeKeyKind::Ability);
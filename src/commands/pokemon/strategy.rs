@@ -5,6 +5,7 @@ use magic::traits::MagicIter;
 
 #[command]
 #[aliases("smogon", "strategy")]
+#[bucket = "expensive"]
 async fn smogon_strategy(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let pokemon = args.rest();
     let aliasized = pokemon.replace(" ", "-").to_lowercase();
@@ -17,21 +18,15 @@ async fn smogon_strategy(ctx: &Context, msg: &Message, args: Args) -> CommandRes
         .await?;
 
     if strategies.is_empty() {
-        let color = crate::read_config().await.color.error;
-        
-        msg.channel_id.send_message(&ctx, |m| {
-            m.embed(|embed| {
-                embed.title(title);
-                embed.description(format!(
-                    "Not found any strategy for the pokemon **{}**",
-                    pokemon
-                ));
-                embed.timestamp(now());
-                embed.color(color);
-                
-                embed
-            })
-        }).await?;
+        let mut embed = themed_embed(msg.guild_id, |c| c.error).await;
+        embed.title(title);
+        embed.description(format!(
+            "Not found any strategy for the pokemon **{}**",
+            pokemon
+        ));
+        embed.timestamp(now());
+
+        msg.channel_id.send_message(&ctx, |m| m.set_embed(embed)).await?;
         return Ok(());
     }
 
@@ -53,23 +48,14 @@ async fn smogon_strategy(ctx: &Context, msg: &Message, args: Args) -> CommandRes
         .map(|v| (v.name.to_owned(), format_moveset(&v), false))
         .collect();
     
-    let color = {
-        let config = crate::read_config().await;
-        config.color.information
-    };
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title(title);
+    embed.description(description);
+    embed.fields(fields);
+    embed.thumbnail(sprite);
+    embed.timestamp(now());
 
-    msg.channel_id.send_message(&ctx, |m| {
-        m.embed(|embed| {
-            embed.title(title);
-            embed.description(description);
-            embed.fields(fields);
-            embed.thumbnail(sprite);
-            embed.timestamp(now());
-            embed.color(color);
-            
-            embed
-        })
-    }).await?;
+    msg.channel_id.send_message(&ctx, |m| m.set_embed(embed)).await?;
 
     Ok(())
 }
@@ -1,3 +1,5 @@
+pub use crate::error::Error;
+pub use crate::slash::Origin;
 pub use crate::storages::*;
 pub use crate::utils::*;
 pub use chrono::Utc;
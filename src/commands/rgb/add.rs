@@ -1,5 +1,4 @@
 use crate::commands::prelude::*;
-use crate::types::GuildConfig;
 
 #[command]
 #[only_in(guilds)]
@@ -18,12 +17,6 @@ async fn add(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
         None => return Ok(())
     };
     
-    let config = crate::read_config().await;
-    let mut guild = config
-        .guilds
-        .entry(guild_id)
-        .or_insert_with(|| GuildConfig::new(guild_id.0));
-
     let mut roles = Vec::new();
     
     for role in &msg.mention_roles {
@@ -32,18 +25,14 @@ async fn add(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
         }
     }
     
-    let count = guild.add_rgb(roles);
+    let count = update_guild_config(&ctx, guild_id, move |guild| guild.add_rgb(roles.clone())).await?;
 
     let response = if count == 0 {
         "Please make sure that you are adding a mentionable roles, which is not in the rgb list yet"
             .to_owned()
     } else {
-        update_guild_config(&ctx, &guild).await?;
         format!("Added {} roles into the almighty RGB database", count)
     };
-    
-    drop(guild);
-    drop(config);
 
     msg.channel_id.say(&ctx, response).await?;
     Ok(())
@@ -6,10 +6,10 @@ use crate::commands::prelude::*;
 #[required_permissions(MANAGE_ROLES)]
 async fn add_member(ctx: &Context, msg: &Message) -> CommandResult {
     if msg.mentions.is_empty() {
-        return Err("Someone must be mentioned to be in the @RGB group".into())
+        return Err(Error::user("Someone must be mentioned to be in the @RGB group").into())
     }
     
-    let guild_id = msg.guild_id.ok_or("This must be use in guilds")?;
+    let guild_id = msg.guild_id.ok_or_else(|| Error::user("This must be used in a server"))?;
     
     let config = crate::read_config().await;
     let guilds = config.guilds.get(&guild_id);
@@ -17,27 +17,21 @@ async fn remove(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
         None => return Ok(())
     };
     
-    let config = crate::read_config().await;
-    let mut guild = match config.guilds.get_mut(&guild_id) {
-        Some(v) => v,
-        None => {
-            msg.channel_id.say(&ctx, "This guild hasn't been rgblized yet...").await?;
-            return Ok(())
+    let roles: Vec<u64> = msg.mention_roles.iter().map(|v| v.0).collect();
+    let count = update_guild_config(&ctx, guild_id, move |guild| {
+        if guild.rgblized.is_none() {
+            return None;
         }
-    };
 
-    let roles = msg.mention_roles.iter().map(|v| v.0);
-    let count = guild.remove_rgb(roles);
+        Some(guild.remove_rgb(roles.iter().copied()))
+    })
+    .await?;
 
-    let response = if count == 0 {
-        "These roles aren't in the RGB list...".to_owned()
-    } else {
-        update_guild_config(&ctx, &guild).await?;
-        format!("Removed {} roles from the almighty RGB database", count)
+    let response = match count {
+        None => "This guild hasn't been rgblized yet...".to_owned(),
+        Some(0) => "These roles aren't in the RGB list...".to_owned(),
+        Some(count) => format!("Removed {} roles from the almighty RGB database", count),
     };
-    
-    drop(guild);
-    drop(config);
 
     msg.channel_id.say(&ctx, response).await?;
     
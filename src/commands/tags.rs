@@ -0,0 +1,30 @@
+use magic::import_all;
+use serenity::framework::standard::macros::group;
+
+import_all! {
+    create,
+    edit,
+    delete,
+    show,
+    list,
+}
+
+#[group]
+#[prefixes("tag", "tags")]
+#[only_in(guilds)]
+#[default_command(list)]
+#[commands(create, edit, delete, show, list)]
+struct Tags;
+
+/// Split `--embed` or `--plain` off the start of the content of a tag
+pub fn embed_flag(content: &str) -> (Option<bool>, &str) {
+    let content = content.trim();
+
+    if let Some(rest) = content.strip_prefix("--embed") {
+        (Some(true), rest.trim())
+    } else if let Some(rest) = content.strip_prefix("--plain") {
+        (Some(false), rest.trim())
+    } else {
+        (None, content)
+    }
+}
@@ -0,0 +1,55 @@
+use super::embed_flag;
+use crate::commands::prelude::*;
+use crate::tags::{self, Tag};
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_MESSAGES)]
+#[min_args(1)]
+#[usage = "{name} ?[--embed] {content}"]
+#[example = "welcome Welcome {user}, read the rules in #rules!"]
+/// Create a custom command of this server, run with the prefix like any command
+/// `{user}`, `{channel}` and `{args}` in the content are replaced by the author, the channel
+/// and what is typed after the name
+/// With `--embed` it is sent in an embed, the files attached to this message are sent along
+async fn create(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let name = tags::tag_name(&args.single::<String>()?)?;
+    let (embed, content) = embed_flag(args.rest());
+    let attachments = msg
+        .attachments
+        .iter()
+        .map(|v| v.url.to_owned())
+        .collect::<Vec<_>>();
+    tags::validate(content, &attachments)?;
+
+    let db = get_db(ctx).await?;
+
+    if tags::get(&db, guild_id, &name).await?.is_some() {
+        let mess = format!(
+            "The tag `{}` already exists, use `tag edit` to change it",
+            name
+        );
+        return Err(Error::user(mess).into());
+    }
+
+    let tag = Tag {
+        content: content.to_owned(),
+        embed: embed.unwrap_or(false),
+        attachments,
+        author: msg.author.id.0,
+        created: Utc::now(),
+        uses: 0,
+    };
+
+    tags::save(&db, guild_id, &name, &tag).await?;
+    msg.channel_id
+        .say(ctx, format!("Created the tag `{}`", name))
+        .await?;
+
+    Ok(())
+}
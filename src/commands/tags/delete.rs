@@ -0,0 +1,30 @@
+use crate::commands::prelude::*;
+use crate::tags;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_MESSAGES)]
+#[num_args(1)]
+#[usage = "{name}"]
+#[example = "welcome"]
+/// Delete a tag of this server
+async fn delete(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let name = args.single::<String>()?.to_lowercase();
+    let db = get_db(ctx).await?;
+
+    if tags::get(&db, guild_id, &name).await?.is_none() {
+        return Err(Error::not_found(format!("There is no tag named `{}`", name)).into());
+    }
+
+    tags::delete(&db, guild_id, &name).await?;
+    msg.channel_id
+        .say(ctx, format!("Deleted the tag `{}`", name))
+        .await?;
+
+    Ok(())
+}
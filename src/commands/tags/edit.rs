@@ -0,0 +1,53 @@
+use super::embed_flag;
+use crate::commands::prelude::*;
+use crate::tags;
+
+#[command]
+#[only_in(guilds)]
+#[required_permissions(MANAGE_MESSAGES)]
+#[min_args(1)]
+#[usage = "{name} ?[--embed | --plain] ?[content]"]
+#[example = "welcome --embed"]
+/// Change a tag of this server
+/// The content is replaced if there is one, the files if some are attached,
+/// and `--embed` or `--plain` switch the way it is sent
+async fn edit(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let name = args.single::<String>()?.to_lowercase();
+    let (embed, content) = embed_flag(args.rest());
+
+    if embed.is_none() && content.is_empty() && msg.attachments.is_empty() {
+        return Err(
+            Error::user("Give a new content, attach files or use `--embed` or `--plain`").into(),
+        );
+    }
+
+    let db = get_db(ctx).await?;
+    let mut tag = tags::get(&db, guild_id, &name)
+        .await?
+        .ok_or_else(|| Error::not_found(format!("There is no tag named `{}`", name)))?;
+
+    if let Some(embed) = embed {
+        tag.embed = embed;
+    }
+
+    if !content.is_empty() {
+        tag.content = content.to_owned();
+    }
+
+    if !msg.attachments.is_empty() {
+        tag.attachments = msg.attachments.iter().map(|v| v.url.to_owned()).collect();
+    }
+
+    tags::validate(&tag.content, &tag.attachments)?;
+    tags::save(&db, guild_id, &name, &tag).await?;
+    msg.channel_id
+        .say(ctx, format!("Changed the tag `{}`", name))
+        .await?;
+
+    Ok(())
+}
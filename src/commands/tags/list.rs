@@ -0,0 +1,35 @@
+use crate::commands::prelude::*;
+use crate::tags;
+use magic::traits::MagicIter as _;
+
+#[command]
+#[only_in(guilds)]
+/// List the tags of this server
+async fn list(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let db = get_db(ctx).await?;
+    let text = tags::list(&db, guild_id)
+        .await?
+        .into_iter()
+        .map(|(name, _)| format!("`{}`", name))
+        .join(", ");
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title("Tags");
+    embed.description(if text.is_empty() {
+        "This server has no tag yet"
+    } else {
+        text.as_str()
+    });
+
+    embed.footer(|f| f.text("Use `tag create {name} {content}` to add one"));
+    embed.timestamp(now());
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::commands::prelude::*;
+use crate::tags;
+
+#[command]
+#[only_in(guilds)]
+#[aliases("raw")]
+#[num_args(1)]
+#[usage = "{name}"]
+#[example = "welcome"]
+/// Show a tag of this server as it is stored, with its placeholders
+async fn show(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let name = args.single::<String>()?.to_lowercase();
+    let db = get_db(ctx).await?;
+    let tag = tags::get(&db, guild_id, &name)
+        .await?
+        .ok_or_else(|| Error::not_found(format!("There is no tag named `{}`", name)))?;
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.title(format!("Tag `{}`", name));
+
+    if !tag.content.is_empty() {
+        embed.description(format!("```\n{}\n```", tag.content.replace("```", "`\u{200b}``")));
+    }
+
+    embed.field("Author", format!("<@{}>", tag.author), true);
+    embed.field("Sent as", if tag.embed { "embed" } else { "message" }, true);
+    embed.field("Uses", tag.uses, true);
+
+    if !tag.attachments.is_empty() {
+        embed.field("Files", tag.attachments.join("\n"), false);
+    }
+
+    embed.footer(|f| f.text("Created"));
+    embed.timestamp(tag.created.to_rfc3339());
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::slash::SlashCommand;
 use magic::import_all;
 use serenity::framework::standard::macros::group;
 
@@ -17,3 +18,6 @@ import_all! {
 #[commands(search, search_image, time, nhentai, corona, translate)]
 #[sub_groups(Ehentai, reminder, genshin)]
 struct Utility;
+
+pub static UTILITY_SLASH_COMMANDS: &[&SlashCommand] =
+    &[&TIME_SLASH, &REMINDER_SLASH, &GENSHIN_TIMER_SLASH];
@@ -1,18 +1,18 @@
 use crate::commands::prelude::*;
 use crate::traits::Paginator;
 use crate::Result;
+use db::Expiring;
 use futures::future::TryFutureExt;
 use magic::traits::MagicIter;
 use serde::{Deserialize, Serialize};
 use serenity::builder::CreateEmbed;
 use smallstr::SmallString;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
 const API: &str = "https://api.covid19api.com/summary";
 const THUMBNAIL: &str = "https://upload.wikimedia.org/wikipedia/commons/thumb/b/b4/Topeka-leaderboard.svg/200px-Topeka-leaderboard.svg.png";
-const CACHE_TIME: u64 = 5 * 60;
+const CACHE_TIME: Duration = Duration::from_secs(5 * 60);
 const DB_KEY: &str = "corona";
-const DB_TIME: &str = "c-time";
 
 #[derive(Default, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -155,48 +155,42 @@ async fn corona(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
 
 async fn get_corona_data(ctx: &Context) -> Result<CoronaSummary> {
     let db = get_data::<DatabaseKey>(&ctx).await.unwrap();
-    let db_ref = db.clone();
-
-    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-    let last: u64 = tokio::task::spawn_blocking(move || db_ref.get(&DB_TIME))
-        .await??
-        .unwrap_or(0);
-
-    if now - last > CACHE_TIME {
-        let res = get_data::<ReqwestClient>(ctx)
-            .await
-            .unwrap()
-            .get(API)
-            .send()
-            .and_then(|v| v.json::<CoronaSummary>())
-            .await;
-
-        if let Ok(mut data) = res {
-            data.countries.sort_by_key(|v| v.total_confirmed);
-            data.countries = data
-                .countries
-                .into_iter()
-                .filter(|v| v.total_confirmed != 0)
-                .rev()
-                .collect();
-
-            tokio::task::block_in_place(|| {
-                let put = db
-                    .insert(&DB_KEY, &data)
-                    .and_then(|_| db.insert(&DB_TIME, &now));
-
-                if let Err(why) = put {
-                    error!("Failed to put the corona data to db\n{:#?}", why);
-                }
-            });
-
-            return Ok(data);
+    let res = db
+        .get_or_fetch(&DB_KEY, CACHE_TIME, || fetch_corona_data(ctx))
+        .await;
+
+    match res {
+        Ok(data) => Ok(data),
+        Err(why) => {
+            error!("Cannot get the corona data\n{:#?}", why);
+
+            // The API is down, an outdated data is still better than nothing
+            db.get_async::<_, Expiring<CoronaSummary>>(&DB_KEY)
+                .await?
+                .map(|v| v.value)
+                .ok_or(why)
         }
     }
+}
 
-    tokio::task::spawn_blocking(move || db.get(&DB_KEY))
-        .await??
-        .ok_or_else(|| Box::new(magic::Error) as Box<_>)
+async fn fetch_corona_data(ctx: &Context) -> Result<CoronaSummary> {
+    let mut data = get_data::<ReqwestClient>(ctx)
+        .await
+        .unwrap()
+        .get(API)
+        .send()
+        .and_then(|v| v.json::<CoronaSummary>())
+        .await?;
+
+    data.countries.sort_by_key(|v| v.total_confirmed);
+    data.countries = data
+        .countries
+        .into_iter()
+        .filter(|v| v.total_confirmed != 0)
+        .rev()
+        .collect();
+
+    Ok(data)
 }
 
 fn code_to_emoji(s: &str) -> String {
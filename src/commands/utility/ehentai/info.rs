@@ -1,7 +1,6 @@
 use crate::commands::prelude::*;
 use crate::traits::Embedable as _;
 use requester::ehentai::EhentaiApi as _;
-use serenity::model::id::ChannelId;
 use crate::Result;
 use crate::types::Ref;
 
@@ -13,7 +12,7 @@ async fn info(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let data = parse_eh_token(content);
 
     if data.is_empty() {
-        send_error(&ctx, msg.channel_id, "Error 404 Not found SadKaede in the content...").await?;
+        send_error(&ctx, msg, "Error 404 Not found SadKaede in the content...").await?;
         return Ok(());
     };
 
@@ -29,7 +28,7 @@ async fn info(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         .collect::<Vec<_>>();
 
     if data.is_empty() {
-        send_error(&ctx, msg.channel_id, "Succesfully Not Found").await?;
+        send_error(&ctx, msg, "Succesfully Not Found").await?;
         return Ok(());
     }
 
@@ -40,23 +39,17 @@ async fn info(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
-async fn send_error(ctx: &Context, channel_id: ChannelId, msg: &str) -> Result<()> {
+async fn send_error(ctx: &Context, msg: &Message, text: &str) -> Result<()> {
     let config = crate::read_config().await;
-    let color = config.color.error;
+    let mut embed = config.theme(msg.guild_id).embed(|c| c.error);
     let thumbnail = config.sadkaede.thumbnail.to_owned();
     drop(config);
-        
-    channel_id.send_message(ctx, |m| {
-        m.embed(|embed| {
-            embed.title("SadKaede information");
-            embed.description(msg);
-
-            embed.color(color);
-            embed.thumbnail(thumbnail);
-
-            embed
-        })
-    }).await?;
+
+    embed.title("SadKaede information");
+    embed.description(text);
+    embed.thumbnail(thumbnail);
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
 
     Ok(())
 }
\ No newline at end of file
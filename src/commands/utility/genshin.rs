@@ -2,6 +2,10 @@ use serenity::framework::standard::macros::group;
 use crate::commands::prelude::*;
 use crate::traits::Embedable;
 use crate::genshin;
+use crate::constants::GENSHIN_WATCH;
+use crate::types::GenshinWatchTree;
+use crate::slash::SlashCommand;
+use futures::future::FutureExt as _;
 
 #[group]
 #[prefixes("genshin", "gi", "paimon")]
@@ -10,19 +14,31 @@ use crate::genshin;
 /// Genshin Impact utilities
 struct Genshin;
 
+pub static GENSHIN_TIMER_SLASH: SlashCommand = SlashCommand {
+    command: &TIMER_COMMAND,
+    name: Some("genshin_timer"),
+    options: &[],
+    run: |ctx, origin, _args| start_timer(ctx, origin).boxed(),
+};
+
 #[command]
 // #[permission(MANAGE_CHANNEL)]
 /// Timer for genshin impact related events
 async fn timer(ctx: &Context, msg: &Message) -> CommandResult {
-    let mess = genshin::RegionsEmbed.send_embed(&ctx, msg.channel_id).await?;
-    let key = msg.channel_id.as_u64();
+    start_timer(ctx, &Origin::from(msg)).await
+}
+
+/// The timer is a normal message, it keeps being edited long after a slash command expires
+async fn start_timer(ctx: &Context, origin: &Origin<'_>) -> CommandResult {
+    origin.hand_over(ctx).await?;
+
+    let channel = origin.channel_id();
+    let mess = genshin::RegionsEmbed.send_embed(&ctx, channel).await?;
+    let key = channel.as_u64();
     let val = mess.id.as_u64();
     
-    get_data::<DatabaseKey>(&ctx)
-        .await
-        .unwrap()
-        .open("genshin_watch")?
-        .insert(key, val)?;
+    let db: GenshinWatchTree = open_tree(ctx, GENSHIN_WATCH).await?;
+    db.insert_async(key, val).await?;
         
     Ok(())
 }
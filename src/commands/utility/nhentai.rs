@@ -42,7 +42,7 @@ async fn nhentai(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
             g.pagination(ctx, msg).await?;
         }
     } else {
-        return Err(format!("Cannot find any with the magic number {}", id.unwrap_or(0)).into())
+        return Err(Error::not_found(format!("Cannot find any with the magic number {}", id.unwrap_or(0))).into())
     };
     
 
@@ -1,8 +1,8 @@
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
+use futures::future::FutureExt as _;
 use serenity::framework::standard::macros::group;
 use magic::import_all;
 
-const DB_KEY: &str = "Reminders";
-
 import_all! {
     set,
     list,
@@ -15,6 +15,36 @@ import_all! {
 #[default_command(set)]
 struct Reminder;
 
+pub static REMINDER_SLASH: SlashCommand = SlashCommand {
+    command: &SET_COMMAND,
+    name: Some("reminder"),
+    options: &[
+        SlashOption {
+            name: "duration",
+            description: "In how long, like 1h 30m",
+            kind: OptionKind::String,
+            required: true,
+        },
+        SlashOption {
+            name: "message",
+            description: "What to remind you of",
+            kind: OptionKind::String,
+            required: false,
+        },
+    ],
+    run: |ctx, origin, args| {
+        let duration = args.string("duration").unwrap_or_default();
+        let message = args.string("message").unwrap_or_default();
 
-
-
+        async move {
+            match humantime::parse_duration(duration) {
+                Ok(v) => remind_in(ctx, origin, v, message).await,
+                Err(_) => {
+                    origin.say(ctx, format!("Cannot parse the duration from `{}`", duration)).await?;
+                    Ok(())
+                }
+            }
+        }
+        .boxed()
+    },
+};
@@ -1,18 +1,16 @@
 use crate::commands::prelude::*;
-use super::DB_KEY;
-use crate::types::Reminder;
+use crate::constants::REMINDERS_BY_USER;
 use magic::traits::MagicIter as _;
 
 #[command]
 /// List all reminders
 async fn list(ctx: &Context, msg: &Message) -> CommandResult {
-    let text = get_data::<DatabaseKey>(ctx)
-        .await
-        .and_then(|v| v.open(&DB_KEY).ok())
-        .ok_or(magic::Error)?
-        .get_all::<i64, Reminder>()
+    let db = open_reminders(ctx).await?;
+    let text = db
+        .get_by_index_async(REMINDERS_BY_USER, &msg.author.id.0)
+        .await?
+        .into_iter()
         .map(|(_, v)| v)
-        .filter(|v| v.user_id == msg.author.id.0)
         .zip(1..)
         .map(|(v, i)| format!("**{}.** *{}* __{}__", i, v.when.format("%F %T UTC"), v.content.unwrap_or_default()))
         .join('\n');
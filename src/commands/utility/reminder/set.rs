@@ -1,49 +1,18 @@
 use crate::commands::prelude::*;
-use crate::types::Reminder;
-use super::DB_KEY;
+use crate::constants::REMINDERS_BY_USER;
+use crate::types::{Reminder, ReminderTree};
+use core::time::Duration;
 use humantime::{format_duration, parse_duration};
 use futures::future::{self, TryFutureExt};
-use std::sync::Arc;
+use serenity::builder::CreateEmbed;
 
 const MAX_LIMIT_DURATION: u64 = 60 * 60 * 24 * 90;
 
 #[command]
 /// Set a reminder
 async fn set(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let dm_check = msg
-        .author
-        .id
-        .create_dm_channel(ctx)
-        .map_err(|_| String::from("Cannot create DM channel to send the reminder"));
-        
-    let db = get_data::<DatabaseKey>(ctx)
-        .await
-        .and_then(|db| db.open(&DB_KEY).ok())
-        .map(Arc::new)
-        .ok_or(magic::Error)?;
-        
-    let author = msg.author.id.0;
-    let db_arc = Arc::clone(&db);
-        
-    let db_check = tokio::task::spawn_blocking(move || {
-        let count = db_arc.get_all::<i64, Reminder>()
-            .filter(|(_, v)| v.user_id == author)
-            .count();
-            
-        if count < 5 {
-            Ok(())
-        } else {
-            Err(String::from("You currently have 5 reminders already"))
-        }
-    })
-    .map_err(|err| err.to_string())
-    .and_then(|v| async move { v });
-        
-    if let Err(why) = future::try_join(dm_check, db_check).await {
-        msg.channel_id.say(ctx, why).await?;
-        return Ok(())
-    }
-    
+    let origin = Origin::from(msg);
+
     args.trimmed();
     
     let mut duration = match args.current().and_then(|s| parse_duration(s).ok()) {
@@ -62,55 +31,137 @@ async fn set(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
         args.advance();
     }
     
+    remind_in(ctx, &origin, duration, args.rest()).await
+}
+
+/// Set a reminder for the author, in `duration` from now
+pub async fn remind_in(
+    ctx: &Context,
+    origin: &Origin<'_>,
+    duration: Duration,
+    message: &str,
+) -> CommandResult {
+    let dm_check = origin
+        .author()
+        .id
+        .create_dm_channel(ctx)
+        .map_err(|_| String::from("Cannot create DM channel to send the reminder"));
+        
+    let db = open_reminders(ctx).await?;
+    let author = origin.author().id.0;
+        
+    let db_check = db
+        .get_by_index_async(REMINDERS_BY_USER, &author)
+        .map_err(|err| err.to_string())
+        .and_then(|reminders| async move {
+            if reminders.len() < 5 {
+                Ok(())
+            } else {
+                Err(String::from("You currently have 5 reminders already"))
+            }
+        });
+        
+    if let Err(why) = future::try_join(dm_check, db_check).await {
+        origin.say(ctx, why).await?;
+        return Ok(())
+    }
+    
     if duration.as_secs() > MAX_LIMIT_DURATION {
-        msg.channel_id.say(ctx, format!("The reminder cannot be greater than 90 days")).await?;
+        origin.say(ctx, "The reminder cannot be greater than 90 days").await?;
         return Ok(())
     }
     
     let chrono_duration = match chrono::Duration::from_std(duration) {
         Ok(d) => d,
         Err(_) => {
-            msg.channel_id.say(ctx, "The duration is *somewhat* invalid for me to process...").await?;
+            origin.say(ctx, "The duration is *somewhat* invalid for me to process...").await?;
             return Ok(())
         }
     };
     
-    let notify = match get_data::<ReminderNotify>(ctx).await {
-        Some(s) => s,
-        None => return Err("The reminder system hasn't initialized yet, please wait a few nanosecond and try again".into()),
-    };
+    let when = Utc::now();
+    let date = when + chrono_duration;
+    let color = when.timestamp() as u64 & 0xffffff;
     
-    let message = args.rest();
-    let reminder = Reminder::new(msg, duration, &message);
-    let date = reminder.when + chrono_duration;
-    let timestamp = date.timestamp();
-    let color = reminder.when.timestamp() as u64 & 0xffffff;
+    let mut embed = CreateEmbed::default();
+    let formated_duration = format_duration(duration);
+    let formated_date = date.format("%F %T UTC");
+    let mess = format!("I will remind you in **{}**", formated_duration);
     
-    tokio::task::spawn_blocking(move || {
-        info!("Got a reminder for {}", &timestamp);
-        db.insert(&timestamp, &reminder)
-    }).await??;
+    embed.description(mess);
+    embed.title(":alarm_clock: Reminder");
+    embed.image("https://cdn.discordapp.com/attachments/450521152272728065/708817978594033804/Diancie.gif");
+    embed.color(color);
+    embed.timestamp(now());
     
-    notify.notify_one();
+    if !message.is_empty() {
+        embed.field("Message", message, false);
+    }
     
-    msg.channel_id.send_message(ctx, move |m| m.embed(move |embed| {
-        let formated_duration = format_duration(duration);
-        let formated_date = date.format("%F %T UTC");
-        let mess = format!("I will remind you in **{}**", formated_duration);
-        
-        embed.description(mess);
-        embed.title(":alarm_clock: Reminder");
-        embed.image("https://cdn.discordapp.com/attachments/450521152272728065/708817978594033804/Diancie.gif");
-        embed.color(color);
-        embed.timestamp(now());
-        
-        if !message.is_empty() {
-            embed.field("Message", message, false);
+    embed.field("Appointment Date", formated_date, false);
+
+    // Sent first, a slash command has no message of its own to link back to
+    let mut answer = origin.send_embed(ctx, embed).await?;
+    let reminder = Reminder::new(origin, origin.link_id(&answer), when, duration, message);
+    
+    let timestamp = match save(&db, date.timestamp(), &reminder).await {
+        Ok(v) => v,
+        Err(why) => {
+            // The answer promised a reminder which will never come
+            let mut embed = CreateEmbed::default();
+            embed.title(":alarm_clock: Reminder");
+            embed.description("I could not save the reminder, please try again later");
+            embed.color(color);
+            
+            origin.edit_embed(ctx, &mut answer, embed).await.ok();
+            return Err(why);
         }
-        
-        embed.field("Appointment Date", formated_date, false);
-        embed
-    })).await?;
+    };
+    
+    info!("Got a reminder for {}", &timestamp);
     
     Ok(())
 }
+
+/// The reminders are keyed by their timestamp, take the next free second
+/// instead of overwriting another reminder set for the same time
+async fn save(db: &ReminderTree, mut timestamp: i64, reminder: &Reminder) -> crate::Result<i64> {
+    while !db.insert_new_async(&timestamp, reminder).await? {
+        timestamp += 1;
+    }
+    
+    Ok(timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::reminder_tree;
+    use db::DbInstance;
+
+    fn reminder(user_id: u64) -> Reminder {
+        Reminder {
+            user_id,
+            msg_id: 1,
+            channel_id: 1,
+            guild_id: None,
+            content: None,
+            when: Utc::now(),
+            duration: Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn take_the_next_free_second() {
+        let db = DbInstance::memory();
+        let reminders = reminder_tree(&db).unwrap();
+
+        assert_eq!(save(&reminders, 100, &reminder(1)).await.unwrap(), 100);
+        assert_eq!(save(&reminders, 100, &reminder(2)).await.unwrap(), 101);
+        assert_eq!(save(&reminders, 100, &reminder(3)).await.unwrap(), 102);
+
+        let first = reminders.get(&100).unwrap().unwrap();
+        assert_eq!(first.user_id, 1);
+        assert_eq!(reminders.len(), 3);
+    }
+}
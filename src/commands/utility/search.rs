@@ -16,9 +16,9 @@ async fn search(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
         .unwrap()
         .google_search(&text)
         .await?;
-    let color = crate::read_config().await.color.information;
+    let embed = themed_embed(msg.guild_id, |c| c.information).await;
     
-    (Search { data, text, color }).pagination(ctx, msg).await?;
+    (Search { data, text, embed }).pagination(ctx, msg).await?;
     
     Ok(())
 }
@@ -26,16 +26,17 @@ async fn search(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
 struct Search {
     data: Vec<GoogleSearchData>,
     text: String,
-    color: u64,
+    /// Each page starts from it
+    embed: CreateEmbed,
 }
 
 impl Paginator for Search {
     fn append_page(&self, page: core::num::NonZeroUsize, embed: &mut CreateEmbed) {
         let data = &self.data[page.get() - 1];
         let description = format!("{}\n[[Link]]({})", data.description, data.link);
+        *embed = self.embed.clone();
         embed.title(format!("Result for `{}`", self.text));
         embed.field(&data.title, description, false);
-        embed.color(self.color);
         embed.footer(|f| f.text(format!("Result {} / {}", page, self.data.len())));
     }
     
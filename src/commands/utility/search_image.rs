@@ -34,6 +34,7 @@ impl From<DuckDuckGoImageData> for ImageSearch {
 
 #[command]
 #[aliases("img", "image")]
+#[bucket = "expensive"]
 async fn search_image(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let text = args.rest();
     let sfw = !is_nsfw_channel(&ctx, msg.channel_id).await;
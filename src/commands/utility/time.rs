@@ -1,6 +1,24 @@
 use crate::commands::prelude::*;
+use crate::slash::{OptionKind, SlashCommand, SlashOption};
 use chrono::TimeZone;
 use chrono_tz::*;
+use futures::future::FutureExt as _;
+use serenity::builder::CreateEmbed;
+
+pub static TIME_SLASH: SlashCommand = SlashCommand {
+    command: &TIME_COMMAND,
+    name: None,
+    options: &[SlashOption {
+        name: "timestamp",
+        description: "Seconds since 01/01/1970, now if not given",
+        kind: OptionKind::Integer,
+        required: false,
+    }],
+    run: |ctx, origin, args| {
+        let time = args.integer("timestamp").unwrap_or_else(|| origin.timestamp().timestamp());
+        send_times(ctx, origin, time).boxed()
+    },
+};
 
 #[command]
 /// Get time for various timezone
@@ -11,6 +29,10 @@ async fn time(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
         .find::<i64>()
         .unwrap_or_else(|_| msg.timestamp.timestamp());
 
+    send_times(ctx, &Origin::from(msg), time).await
+}
+
+async fn send_times(ctx: &Context, origin: &Origin<'_>, time: i64) -> CommandResult {
     let utc = UTC.timestamp(time, 0);
     let times = vec![
         ("Pacific", utc.with_timezone(&US::Pacific)),
@@ -24,17 +46,17 @@ async fn time(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     let format = config.time.format.to_owned();
     drop(config);
     
-    msg.channel_id.send_message(ctx, |m| m.embed(|embed| {
-        for (name, time) in times {
-            let tz = &time.format("%:z").to_string()[..3];
-            let embed_name = format!("{} (GMT{})", name, tz);
-            let embed_value = time.format(&format);
-            embed.field(embed_name, embed_value.to_string(), false);
-        }
-        
-        embed.timestamp(msg.timestamp.to_rfc3339());
-        embed
-    })).await?;
+    let mut embed = CreateEmbed::default();
+
+    for (name, time) in times {
+        let tz = &time.format("%:z").to_string()[..3];
+        let embed_name = format!("{} (GMT{})", name, tz);
+        let embed_value = time.format(&format);
+        embed.field(embed_name, embed_value.to_string(), false);
+    }
+    
+    embed.timestamp(origin.timestamp().to_rfc3339());
+    origin.send_embed(ctx, embed).await?;
 
     Ok(())
 }
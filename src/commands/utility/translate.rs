@@ -33,6 +33,7 @@ impl Embedable for Translated {
 
 #[command]
 #[aliases("trans", "t")]
+#[bucket = "expensive"]
 /// Translate text using google translator
 async fn translate(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let text = args.rest().to_owned();
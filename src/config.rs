@@ -1,22 +1,75 @@
 use crate::types::GuildConfig;
 use crate::Result;
 use dashmap::DashMap;
-use db::DbInstance;
 use lib_config::{Config as LibConfig, Environment, File, FileFormat};
 use serde::{Deserialize, Serialize};
-use serenity::model::id::{EmojiId, GuildId, UserId};
+use serde_json::Value;
+use serenity::builder::CreateEmbed;
+use serenity::model::id::{ChannelId, EmojiId, GuildId, UserId};
 use smallstr::SmallString;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use futures::AsyncWriteExt as _;
 
+mod validate;
+
+pub use validate::{ConfigIssue, ConfigReport};
+
+/// The config file, read on startup and watched for changes afterward
+pub const CONFIG_PATH: &str = "./config.toml";
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Database {
     pub path: PathBuf,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+pub struct Snapshot {
+    pub dir: PathBuf,
+    /// Seconds between two snapshots, 0 to disable them
+    /// Read once on startup
+    pub interval: u64,
+    /// How many snapshots are kept, the oldest ones are removed first
+    pub keep: usize,
+}
+
+/// Where and how often the grouped error reports are sent
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ErrorReport {
+    /// Instead of a DM to the owners
+    pub channel: Option<ChannelId>,
+    /// Seconds between two reports, 0 to disable them
+    pub interval: u64,
+    /// How many groups of errors are shown in one report
+    pub max_entries: usize,
+}
+
+/// The slash commands, registered on every startup
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Slash {
+    pub enable: bool,
+    /// Register them only in this guild, where they show up at once, instead of globally
+    pub guild: Option<GuildId>,
+}
+
+impl Default for Slash {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            guild: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Stats {
+    /// How many days the command invocations are kept, 0 to keep them forever
+    pub keep_days: u64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct Color {
     pub information: u64,
     pub success: u64,
@@ -71,6 +124,18 @@ pub struct ApiKeys {
 pub struct FacebookPage {
     pub id: u64,
     pub token: String,
+    /// Where the sauces can be posted to the page, nothing is posted when unset
+    pub guild: Option<GuildId>,
+    /// Who can post, by reacting to the sauce
+    #[serde(default)]
+    pub authors: Vec<FacebookAuthor>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FacebookAuthor {
+    pub id: UserId,
+    /// Signed at the end of the posts
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -78,6 +143,8 @@ pub struct Rgb {
     pub evidence: PathBuf,
     pub tu: Vec<SmallString<[u8; 8]>>,
     pub tu_server: Vec<GuildId>,
+    /// The one `rgb_tu` answers to, it is disabled when unset
+    pub tu_user: Option<UserId>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -105,6 +172,36 @@ pub struct Nhentai {
     pub wait_duration: u16,
 }
 
+/// Who shares the uses of a bucket
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BucketScope {
+    User,
+    Channel,
+    Guild,
+}
+
+/// A rate limit, applied to the commands with `#[bucket = "{name}"]`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Bucket {
+    #[serde(rename = "for")]
+    pub scope: BucketScope,
+    /// Seconds between two uses
+    #[serde(default)]
+    pub delay: u64,
+    /// At most `limit` uses every `time_span` seconds
+    #[serde(default)]
+    pub time_span: u64,
+    #[serde(default)]
+    pub limit: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Console {
+    /// Where the messages typed in the console go before any message is received
+    pub channel: Option<ChannelId>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Time {
     pub format: SmallString<[u8; 24]>,
@@ -159,11 +256,23 @@ pub struct Config {
     pub respect_emoji: Option<EmojiId>,
     pub radio_stations: Option<PathBuf>,
     pub disable_auto_cmd: Vec<SmallString<[u8; 14]>>,
+    /// Allowed to use the owner-only commands, the owner of the application when empty
+    /// Read once on startup
+    #[serde(default)]
+    pub owners: HashSet<UserId>,
     #[serde(default)]
     pub masters: HashSet<UserId>,
+    /// Found on startup, a master whatever the file says
+    #[serde(skip)]
+    pub app_owner: Option<UserId>,
     pub rgb: Option<Rgb>,
     pub tmq: Option<TouhouMusicQuest>,
     pub database: Database,
+    pub snapshot: Snapshot,
+    pub stats: Stats,
+    pub error_report: ErrorReport,
+    #[serde(default)]
+    pub slash: Slash,
     pub color: Color,
     pub emoji: Emoji,
     pub time: Time,
@@ -171,111 +280,364 @@ pub struct Config {
     pub sadkaede: SadKaede,
     pub nhentai: Nhentai,
     #[serde(default)]
+    pub console: Console,
+    /// Read once on startup
+    #[serde(default)]
+    pub buckets: BTreeMap<String, Bucket>,
+    #[serde(default)]
     pub guilds: DashMap<GuildId, GuildConfig>,
     #[serde(default)]
     pub apikeys: ApiKeys,
 }
 
-//pub struct SaveOption {
-//    pub path: PathBuf,
-// pub file_type: FileType,
-//}
+/// The look of the embeds in a guild, see `Config::theme`
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub color: Color,
+    pub thumbnail: Option<String>,
+    pub footer: Option<String>,
+}
+
+impl Theme {
+    /// A new embed in one of the colors of the theme, with the thumbnail and the footer of the guild
+    /// An embed setting its own thumbnail or footer replaces them
+    pub fn embed(&self, color: fn(&Color) -> u64) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        embed.color(color(&self.color));
 
-// pub enum FileType { Json, Toml }
-//
-// impl fmt::Display for FileType {
-// fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-// match self {
-// Self::Json => write!(f, "json"),
-// Self::Toml => write!(f, "toml"),
-// }
-// }
-// }
+        if let Some(url) = &self.thumbnail {
+            embed.thumbnail(url);
+        }
+
+        if let Some(text) = &self.footer {
+            embed.footer(|f| f.text(text));
+        }
+
+        embed
+    }
+}
 
 impl Config {
+    /// The look of the embeds in a guild, its own theme on top of the global colors
+    pub fn theme(&self, guild: Option<GuildId>) -> Theme {
+        let mut theme = Theme {
+            color: self.color,
+            thumbnail: None,
+            footer: None,
+        };
+
+        let guild = match guild.and_then(|v| self.guilds.get(&v)) {
+            Some(g) => g,
+            None => return theme,
+        };
+
+        let custom = &guild.theme;
+        let color = &mut theme.color;
+
+        color.information = custom.information.unwrap_or(color.information);
+        color.success = custom.success.unwrap_or(color.success);
+        color.error = custom.error.unwrap_or(color.error);
+        color.message_update = custom.message_update.unwrap_or(color.message_update);
+        color.message_delete = custom.message_delete.unwrap_or(color.message_delete);
+        color.lovely = custom.lovely.unwrap_or(color.lovely);
+
+        theme.thumbnail = custom.thumbnail.clone();
+        theme.footer = custom.footer.clone();
+        theme
+    }
+
     /// Initial the config
     /// This will read config from DefaultConfig > Config file (if exist) > Environment
     /// Data in environment variable will have priority over the config file
     pub fn init() -> Result<Self> {
+        Self::load(Path::new(CONFIG_PATH), false, true)
+    }
+
+    /// Read the default config, overridden by the file at `path` (TOML or JSON by its extension),
+    /// then by the environment if `env`
+    fn load(path: &Path, required: bool, env: bool) -> Result<Self> {
         let mut config = LibConfig::new();
         let default_config = include_str!("../assets/data/default_config.toml");
+        let name = path.to_str().ok_or("The config path is not valid UTF-8")?;
 
         config.merge(File::from_str(default_config, FileFormat::Toml))?;
-        config.merge(File::with_name("./config.toml").required(false))?;
-        config.merge(Environment::new())?;
+        config.merge(File::new(name, file_format(path)?).required(required))?;
+
+        if env {
+            config.merge(Environment::new())?;
+        }
 
         let res = config.try_into()?;
         Ok(res)
     }
 
-    /// Perform the same action as `init`, but replace self instead of create new
-    /// for some reason, keeping the guilds config will make it crash
-    /// that's why creating the new one instead and then fetch the guilds config
-    /// from the database *again*, because of this, the DbInstance is needed
-    /// This *maybe* fix in the future
-    pub fn reload(&mut self, db: &DbInstance) -> Result<()> {
-        let mut config = LibConfig::new();
-        let default_config = include_str!("../assets/data/default_config.toml");
+    /// Fail with every error found by `check`, the warnings are only logged
+    pub fn validate(&self) -> Result<()> {
+        let report = self.check();
 
-        config.merge(File::from_str(default_config, FileFormat::Toml))?;
-        config.merge(File::with_name("./config.toml"))?;
-        config.merge(Environment::new())?;
+        for issue in &report.warnings {
+            warn!("Config {}", issue);
+        }
 
-        *self = config.try_into()?;
+        if report.is_ok() {
+            Ok(())
+        } else {
+            Err(Box::new(report))
+        }
+    }
+
+    /// Swap in a freshly loaded config, the guild configs (which live in the database) are moved into it
+    /// Return the previous config, left without them
+    pub fn replace(&mut self, mut new: Config) -> Config {
+        new.guilds = std::mem::take(&mut self.guilds);
+        std::mem::replace(self, new)
+    }
 
-        let db_data = db.open("GuildConfig")?.get_all::<GuildId, GuildConfig>();
-        let guilds_config = &self.guilds;
+    /// Compare every field except the guild configs
+    pub fn diff(&self, other: &Config) -> Vec<ConfigChange> {
+        Self::changes(&self.comparable(), &other.comparable())
+    }
 
-        for (k, v) in db_data {
-            guilds_config.insert(k, v);
+    /// The config as `diff` compares it, the guild configs are still serialized
+    pub fn comparable(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+
+        // A set has no order, so only its content is compared
+        for key in &["masters", "owners"] {
+            if let Some(Value::Array(users)) = value.get_mut(*key) {
+                users.sort_by_key(|v| v.to_string());
+            }
         }
 
-        Ok(())
+        value
     }
 
-    /// Save config to file using JSON format
-    /// If the provided path is a directory, it will create a `config_{timestamp}.json` inside of that
-    /// If the file exists, this will add a `.bak` to that file and do the work.
+    /// The fields which differ between two `comparable` configs
+    pub fn changes(old: &Value, new: &Value) -> Vec<ConfigChange> {
+        let mut res = Vec::new();
+        diff_value("", Some(old), Some(new), &mut res);
+        res
+    }
+
+    /// Save config to a file, as TOML or JSON depending on its extension
+    /// If the provided path is a directory, it will create a `config_{timestamp}.toml` inside of that
+    /// If the file exists, a copy of it is kept with a `.bak` added to its name.
+    /// The secrets are replaced by `REDACTED` unless `show_secrets`.
+    /// The config is written into a temporary file next to it, which is read back
+    /// to make sure it gives the same config, then renamed over the real file,
+    /// so the file is never left half written
     /// Return the `PathBuf` of the saved file
-    pub async fn save_file<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+    pub async fn save_file<P: AsRef<Path>>(&self, path: P, show_secrets: bool) -> Result<PathBuf> {
         let mut path = path.as_ref().to_path_buf();
 
         if path.is_dir() {
             let time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
-            path.push(format!("config_{}.json", time.as_millis()));
+            path.push(format!("config_{}.toml", time.as_millis()));
         }
 
-        if fs::metadata(&path).await.is_ok() {
-            let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        let data = self.to_string(file_format(&path)?, show_secrets)?;
+        let file_name = path.file_name().unwrap_or_default().to_os_string();
+
+        // Prefixed instead of suffixed, the extension tells the format when loading it back
+        let mut tmp_name = std::ffi::OsString::from(".tmp_");
+        tmp_name.push(&file_name);
+
+        let mut tmp_path = path.clone();
+        tmp_path.set_file_name(tmp_name);
 
-            file_name.push(".bak");
+        if let Err(why) = self.write_checked(&tmp_path, &data, show_secrets).await {
+            fs::remove_file(&tmp_path).await.ok();
+            return Err(why);
+        }
+
+        if fs::metadata(&path).await.is_ok() {
+            let mut bak_name = file_name;
+            bak_name.push(".bak");
 
-            let mut new_path = path.clone();
-            new_path.set_file_name(file_name);
+            let mut bak_path = path.clone();
+            bak_path.set_file_name(bak_name);
 
-            fs::rename(&path, new_path).await?;
+            fs::copy(&path, bak_path).await?;
         }
 
-        let data = serde_json::to_vec_pretty(self)?;
-        let mut file = fs::File::create(&path).await?;
-
-        file.write_all(data.as_slice()).await?;
-
-        // match path.extension().and_then(|v| v.to_str()) {
-        // Some(v) if v == "json" => {
-        // serde_json::to_writer_pretty(&mut file, self)?
-        // }
-        //
-        // Some(v) if v == "toml" => {
-        //    convert it to json value first and then convert back to toml
-        //    this is to avoid the `KeyNotString` error
-        // let value = serde_json::to_value(self)?;
-        // let data = toml::to_string_pretty(&value)?;
-        // file.write_all(data.as_bytes())?
-        // }
-        // _ => {}
-        // }
+        fs::rename(&tmp_path, &path).await?;
 
         Ok(path)
     }
+
+    /// Write the data into the file then check that it loads back into this config
+    async fn write_checked(&self, path: &Path, data: &str, show_secrets: bool) -> Result<()> {
+        let mut file = fs::File::create(path).await?;
+
+        file.write_all(data.as_bytes()).await?;
+        file.flush().await?;
+        file.sync_all().await?;
+
+        let saved_path = path.to_path_buf();
+        let saved = tokio::task::spawn_blocking(move || Self::load(&saved_path, true, false)).await??;
+
+        let lost: Vec<_> = self
+            .diff(&saved)
+            .into_iter()
+            .filter(|v| show_secrets || !v.is_secret())
+            .map(|v| v.to_string())
+            .collect();
+
+        if !lost.is_empty() {
+            let err = format!("The saved config does not load back the same\n{}", lost.join("\n"));
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    /// Serialize the config without the guild configs, which live in the database
+    pub fn to_string(&self, format: FileFormat, show_secrets: bool) -> Result<String> {
+        let mut value = serde_json::to_value(self)?;
+
+        if let Value::Object(map) = &mut value {
+            map.remove("guilds");
+        }
+
+        if !show_secrets {
+            for secret in SECRETS {
+                let pointer = format!("/{}", secret.replace('.', "/"));
+
+                if let Some(v) = value.pointer_mut(&pointer).filter(|v| !v.is_null()) {
+                    *v = Value::String(REDACTED.to_owned());
+                }
+            }
+        }
+
+        match format {
+            FileFormat::Json => Ok(serde_json::to_string_pretty(&value)?),
+            FileFormat::Toml => {
+                // TOML has no null, and its tables must come after the plain values,
+                // which `toml::Value` takes care of
+                remove_nulls(&mut value);
+                let value = toml::Value::try_from(value)?;
+                Ok(toml::to_string_pretty(&value)?)
+            }
+
+            _ => Err("Only TOML and JSON are supported".into()),
+        }
+    }
+}
+
+/// The fields hidden when saving the config, and in the reload reports
+const SECRETS: &[&str] = &[
+    "apikeys.google",
+    "apikeys.osu",
+    "apikeys.facebook.token",
+    "sadkaede.cookie",
+];
+
+/// The fields only read on startup, their changes wait for a restart
+const RESTART: &[&str] = &["owners", "buckets", "slash", "snapshot.interval"];
+
+/// What the secrets are replaced with
+pub const REDACTED: &str = "REDACTED";
+
+fn file_format(path: &Path) -> Result<FileFormat> {
+    match path.extension().and_then(|v| v.to_str()) {
+        Some("toml") => Ok(FileFormat::Toml),
+        Some("json") => Ok(FileFormat::Json),
+        _ => Err(format!("{} is neither a .toml nor a .json file", path.display()).into()),
+    }
+}
+
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+
+        Value::Array(values) => values.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
+/// A field of the config which has a different value, given by its dotted path
+#[derive(Debug, Clone)]
+pub struct ConfigChange {
+    pub path: String,
+    /// `None` when the field was not set
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl ConfigChange {
+    /// The secrets are never shown, only that they have changed
+    /// Neither is a whole table containing one, as when it goes from unset to set
+    pub fn is_secret(&self) -> bool {
+        SECRETS.iter().any(|v| overlaps(&self.path, v))
+    }
+
+    /// Changed in the config, but not applied before the bot is restarted
+    pub fn needs_restart(&self) -> bool {
+        RESTART.iter().any(|v| overlaps(&self.path, v))
+    }
+}
+
+/// Either path is a field within the other one, or the same field
+fn overlaps(a: &str, b: &str) -> bool {
+    let within = |inner: &str, outer: &str| {
+        outer.is_empty()
+            || inner == outer
+            || inner.strip_prefix(outer).map_or(false, |v| v.starts_with('.'))
+    };
+
+    within(a, b) || within(b, a)
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<Value>| match v {
+            Some(Value::Null) | None => String::from("*unset*"),
+            Some(v) => format!("`{}`", v),
+        };
+
+        if self.is_secret() {
+            write!(f, "`{}` has changed", self.path)?;
+        } else {
+            write!(f, "`{}`: {} → {}", self.path, show(&self.old), show(&self.new))?;
+        }
+
+        if self.needs_restart() {
+            f.write_str(" *(after a restart)*")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn diff_value(path: &str, old: Option<&Value>, new: Option<&Value>, res: &mut Vec<ConfigChange>) {
+    match (old, new) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+
+            for key in keys {
+                if path.is_empty() && key == "guilds" {
+                    continue;
+                }
+
+                let path = if path.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+
+                diff_value(&path, a.get(key), b.get(key), res);
+            }
+        }
+
+        (a, b) if a != b => res.push(ConfigChange {
+            path: path.to_owned(),
+            old: a.cloned(),
+            new: b.cloned(),
+        }),
+
+        _ => {}
+    }
 }
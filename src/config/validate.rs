@@ -0,0 +1,280 @@
+use super::{Config, PokemonEmoji};
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
+use serenity::model::channel::ReactionType;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// A problem found in the config, with the dotted path of its field
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    pub field: String,
+    pub problem: String,
+}
+
+/// Every problem found by `Config::check`
+/// The errors make the config unusable, the warnings only disable some features
+#[derive(Debug, Default)]
+pub struct ConfigReport {
+    pub errors: Vec<ConfigIssue>,
+    pub warnings: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    fn error(&mut self, field: impl Into<String>, problem: impl Into<String>) {
+        self.errors.push(ConfigIssue {
+            field: field.into(),
+            problem: problem.into(),
+        });
+    }
+
+    fn warn(&mut self, field: impl Into<String>, problem: impl Into<String>) {
+        self.warnings.push(ConfigIssue {
+            field: field.into(),
+            problem: problem.into(),
+        });
+    }
+
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// A custom emoji (`<:name:id>`) or a single unicode emoji
+    fn emoji(&mut self, field: &str, emoji: &str) {
+        let is_emoji = match emoji.parse::<ReactionType>() {
+            Ok(ReactionType::Custom { .. }) => true,
+            Ok(ReactionType::Unicode(v)) => is_unicode_emoji(&v),
+            _ => false,
+        };
+
+        if !is_emoji {
+            self.error(field, format!("`{}` is not an emoji", emoji));
+        }
+    }
+
+    fn file(&mut self, field: &str, path: &Path) {
+        if !path.is_file() {
+            self.error(field, format!("{} is not a file", path.display()));
+        }
+    }
+
+    fn dir(&mut self, field: &str, path: &Path) {
+        if !path.is_dir() {
+            self.error(field, format!("{} is not a directory", path.display()));
+        }
+    }
+
+    fn color(&mut self, field: &str, color: u64) {
+        if color > 0xffffff {
+            self.error(field, format!("{:#x} is bigger than 0xffffff", color));
+        }
+    }
+
+    fn api_key(&mut self, field: &str, key: Option<&String>, feature: &str) {
+        match key {
+            None => self.warn(field, format!("not set, {} is disabled", feature)),
+            Some(k) if k.trim().is_empty() => self.error(field, "is empty"),
+            Some(_) => {}
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.field, self.problem)
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.errors.is_empty() {
+            writeln!(f, "The config has {} errors", self.errors.len())?;
+
+            for issue in &self.errors {
+                writeln!(f, "- {}", issue)?;
+            }
+        }
+
+        if !self.warnings.is_empty() {
+            writeln!(f, "The config has {} warnings", self.warnings.len())?;
+
+            for issue in &self.warnings {
+                writeln!(f, "- {}", issue)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ConfigReport {}
+
+impl Config {
+    /// Look for every value which would only fail later at runtime
+    pub fn check(&self) -> ConfigReport {
+        let mut report = ConfigReport::default();
+
+        if self.prefix.is_empty() {
+            report.error("prefix", "cannot be empty");
+        }
+
+        if self.master_prefix.is_empty() {
+            report.error("master_prefix", "cannot be empty");
+        }
+
+        report.file("eliza_brain", Path::new(&self.eliza_brain));
+
+        if let Some(path) = &self.radio_stations {
+            report.file("radio_stations", path);
+        }
+
+        if let Some(path) = &self.temp_dir {
+            if path.exists() && !path.is_dir() {
+                report.error("temp_dir", format!("{} is not a directory", path.display()));
+            }
+        }
+
+        if let Some(tmq) = &self.tmq {
+            report.dir("tmq.source", &tmq.source);
+            report.file("tmq.emoji", &tmq.emoji);
+
+            if tmq.duration <= 0.0 {
+                report.error("tmq.duration", "must be positive");
+            }
+        }
+
+        if let Some(rgb) = &self.rgb {
+            report.dir("rgb.evidence", &rgb.evidence);
+
+            if rgb.tu_user.is_none() {
+                report.warn("rgb.tu_user", "not set, rgb_tu is disabled");
+            }
+        }
+
+        report.emoji("emoji.sauce", &self.emoji.sauce);
+        report.emoji("emoji.sadkaede", &self.emoji.sadkaede);
+        report.emoji("emoji.nhentai", &self.emoji.nhentai);
+
+        if let Some(pokemon) = &self.emoji.pokemon {
+            check_pokemon_emoji(&mut report, pokemon);
+        }
+
+        let colors = [
+            ("color.information", self.color.information),
+            ("color.success", self.color.success),
+            ("color.error", self.color.error),
+            ("color.message_update", self.color.message_update),
+            ("color.message_delete", self.color.message_delete),
+            ("color.lovely", self.color.lovely),
+        ];
+
+        for (field, color) in colors.iter() {
+            report.color(field, *color);
+        }
+
+        if StrftimeItems::new(&self.time.format).any(|v| v == Item::Error) {
+            report.error("time.format", format!("`{}` is not a valid time format", self.time.format));
+        }
+
+        for (i, zone) in self.time.zones.iter().enumerate() {
+            if zone.zone.parse::<Tz>().is_err() {
+                let field = format!("time.zones[{}].zone", i);
+                report.error(field, format!("`{}` is not a timezone", zone.zone));
+            }
+        }
+
+        for (name, bucket) in &self.buckets {
+            if bucket.time_span > 0 && bucket.limit == 0 {
+                report.error(format!("buckets.{}.limit", name), "must be positive with a time_span");
+            }
+        }
+
+        for name in &["basic", "expensive"] {
+            if !self.buckets.contains_key(*name) {
+                report.warn(format!("buckets.{}", name), "not set, its commands are not rate limited");
+            }
+        }
+
+        if self.error_report.max_entries == 0 || self.error_report.max_entries > 25 {
+            report.error("error_report.max_entries", "must be between 1 and 25, the fields of an embed");
+        }
+
+        if self.snapshot.keep == 0 {
+            report.error("snapshot.keep", "must keep at least one snapshot");
+        }
+
+        report.api_key("apikeys.google", self.apikeys.google.as_ref(), "the Google search");
+        report.api_key("apikeys.osu", self.apikeys.osu.as_ref(), "the osu! commands");
+
+        match &self.apikeys.facebook {
+            Some(page) if page.token.trim().is_empty() => {
+                report.error("apikeys.facebook.token", "is empty")
+            }
+            Some(page) if page.guild.is_none() => {
+                report.warn("apikeys.facebook.guild", "not set, posting to the page is disabled")
+            }
+            Some(page) if page.authors.is_empty() => {
+                report.warn("apikeys.facebook.authors", "empty, posting to the page is disabled")
+            }
+            Some(_) => {}
+            None => report.warn("apikeys.facebook", "not set, posting to the page is disabled"),
+        }
+
+        report
+    }
+}
+
+/// The longest emojis are the sequences joined with zero width joiners, eg. the families
+const MAX_EMOJI_CHARS: usize = 16;
+
+/// Not the full unicode list, but enough to refuse any text which is not an emoji:
+/// it must start with a pictograph, a regional indicator (the flags) or a keycap base,
+/// and have no ASCII letter nor whitespace in it
+fn is_unicode_emoji(s: &str) -> bool {
+    let first = match s.chars().next() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    let is_keycap = matches!(first, '0'..='9' | '#' | '*') && s.contains('\u{20E3}');
+    let is_pictograph = matches!(
+        first,
+        '\u{00A9}' | '\u{00AE}' | '\u{203C}'..='\u{3299}' | '\u{1F000}'..='\u{1FAFF}'
+    );
+
+    (is_keycap || is_pictograph)
+        && s.chars().count() <= MAX_EMOJI_CHARS
+        && !s.chars().any(|c| c.is_ascii_alphabetic() || c.is_whitespace())
+}
+
+fn check_pokemon_emoji(report: &mut ConfigReport, emoji: &PokemonEmoji) {
+    let fields = [
+        ("physical", &emoji.physical),
+        ("special", &emoji.special),
+        ("status", &emoji.status),
+        ("normal", &emoji.normal),
+        ("fire", &emoji.fire),
+        ("water", &emoji.water),
+        ("grass", &emoji.grass),
+        ("poison", &emoji.poison),
+        ("dark", &emoji.dark),
+        ("ghost", &emoji.ghost),
+        ("steel", &emoji.steel),
+        ("fighting", &emoji.fighting),
+        ("flying", &emoji.flying),
+        ("dragon", &emoji.dragon),
+        ("rock", &emoji.rock),
+        ("ground", &emoji.ground),
+        ("psychic", &emoji.psychic),
+        ("electric", &emoji.electric),
+        ("ice", &emoji.ice),
+        ("bug", &emoji.bug),
+        ("fairy", &emoji.fairy),
+    ];
+
+    for (name, value) in fields.iter() {
+        report.emoji(&format!("emoji.pokemon.{}", name), value);
+    }
+}
@@ -0,0 +1,85 @@
+//! Reload the config file whenever it is modified
+
+use crate::config::{Config, ConfigChange, CONFIG_PATH};
+use crate::Result;
+use core::time::Duration;
+use magic::traits::{MagicIter as _, MagicStr as _};
+use serenity::http::Http;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// How often the modification time of the config file is checked
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Load the config file again, and swap it in only if it is valid
+/// Return what has changed
+pub async fn reload() -> Result<Vec<ConfigChange>> {
+    let mut new = tokio::task::spawn_blocking(Config::init).await??;
+    new.validate()?;
+
+    // The guild configs live in the database, those of the file are never used
+    new.guilds.clear();
+    new.app_owner = crate::read_config().await.app_owner;
+    new.masters.extend(new.app_owner);
+
+    // Serialized outside of the lock, the new config before the swap and the old one once
+    // its guild configs have been moved out
+    let after = new.comparable();
+    let old = crate::write_config().await.replace(new);
+
+    Ok(Config::changes(&old.comparable(), &after))
+}
+
+async fn modified() -> Option<SystemTime> {
+    fs::metadata(CONFIG_PATH).await.ok()?.modified().ok()
+}
+
+/// Watch the config file, reloading it and sending the changes to the owner of the bot
+pub async fn watch(http: Arc<Http>) {
+    let mut last = modified().await;
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let current = modified().await;
+
+        if current.is_none() || current == last {
+            continue;
+        }
+
+        last = current;
+
+        let changes = match reload().await {
+            Ok(v) => v,
+            Err(why) => {
+                error!("Rejected the modified {}\n{}", CONFIG_PATH, why);
+                continue;
+            }
+        };
+
+        if changes.is_empty() {
+            continue;
+        }
+
+        info!("Reloaded {}, {} changes", CONFIG_PATH, changes.len());
+
+        if let Err(why) = report(&http, &changes).await {
+            error!("Cannot send the config changes to the owner\n{:?}", why);
+        }
+    }
+}
+
+async fn report(http: &Arc<Http>, changes: &[ConfigChange]) -> Result<()> {
+    let owner = http.get_current_application_info().await?.owner;
+    let text = changes.iter().map(|v| v.to_string()).join('\n');
+
+    let mut embed = crate::utils::themed_embed(None, |c| c.information).await;
+    embed.title(format!("Reloaded {}", CONFIG_PATH));
+    embed.description(text.split_at_limit(2000, "\n").next().unwrap_or_default());
+
+    owner
+        .direct_message(Arc::clone(http), |m| m.set_embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -1,5 +1,14 @@
 pub const POKEMON_MOVE_PER_PAGE: usize = 8;
 
+/// How often the expired cache entries are removed from the database
+pub const SWEEP_INTERVAL: core::time::Duration = core::time::Duration::from_secs(60 * 60);
+
 // Database keys
+pub const GUILD_CONFIG: &str = "GuildConfig";
+pub const REMINDERS: &str = "Reminders";
+pub const REMINDERS_BY_USER: &str = "user";
+pub const GENSHIN_WATCH: &str = "genshin_watch";
 pub const SMOGON_POKEMON: &str = "spkm";
 pub const SMOGON_DESCRIPTION: &str = "sdesc";
+pub const COMMAND_STATS: &str = "command_stats";
+pub const TAGS: &str = "tags";
@@ -0,0 +1,151 @@
+//! What went wrong, told to the users only as far as it concerns them
+//!
+//! `crate::Result` stays a boxed error so `?` works with every library,
+//! the commands return an `Error` for the failures they understand,
+//! and `user_message` turns anything else into a generic message, the details are only logged
+
+use serenity::framework::standard::ArgError;
+use serenity::model::ModelError;
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt;
+use std::num::{ParseFloatError, ParseIntError};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input is wrong, the message tells how to fix it
+    User(String),
+    /// What the user asked for does not exist
+    NotFound(String),
+    /// The user, or the bot, is not allowed to do it
+    Permission(String),
+    /// An external service failed or answered with garbage
+    Upstream {
+        service: &'static str,
+        source: BoxError,
+    },
+    /// A bug or a broken state of the bot
+    Internal(String),
+}
+
+impl Error {
+    pub fn user(message: impl Into<String>) -> Self {
+        Self::User(message.into())
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn permission(message: impl Into<String>) -> Self {
+        Self::Permission(message.into())
+    }
+
+    pub fn upstream(service: &'static str, source: impl Into<BoxError>) -> Self {
+        Self::Upstream {
+            service,
+            source: source.into(),
+        }
+    }
+
+    pub fn internal(context: impl Into<String>) -> Self {
+        Self::Internal(context.into())
+    }
+
+    /// Whether the failure is on the side of the bot, and worth telling its owners
+    pub fn is_internal(&self) -> bool {
+        matches!(self, Self::Internal(_))
+    }
+
+    /// What the user is told, without any detail of the internals
+    pub fn user_message(&self) -> String {
+        match self {
+            Self::User(v) | Self::NotFound(v) | Self::Permission(v) => v.to_owned(),
+            Self::Upstream { service, .. } => {
+                format!("{} is not answering right now, please try again later", service)
+            }
+            Self::Internal(_) => String::from(INTERNAL_MESSAGE),
+        }
+    }
+}
+
+const INTERNAL_MESSAGE: &str = "Something went wrong on my side, it has been reported to my owners";
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(v) => write!(f, "Invalid input: {}", v),
+            Self::NotFound(v) => write!(f, "Not found: {}", v),
+            Self::Permission(v) => write!(f, "Missing permission: {}", v),
+            Self::Upstream { service, source } => write!(f, "{} failed: {}", service, source),
+            Self::Internal(v) => write!(f, "Internal error: {}", v),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Upstream { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+/// Look into an error returned by a command
+/// The errors from the libraries are sorted out as well as possible, the rest is internal
+pub fn classify(err: &(dyn StdError + Send + Sync + 'static)) -> Error {
+    if let Some(e) = err.downcast_ref::<Error>() {
+        return match e {
+            Error::User(v) => Error::User(v.to_owned()),
+            Error::NotFound(v) => Error::NotFound(v.to_owned()),
+            Error::Permission(v) => Error::Permission(v.to_owned()),
+            Error::Upstream { service, source } => Error::upstream(*service, source.to_string()),
+            Error::Internal(v) => Error::Internal(v.to_owned()),
+        };
+    }
+
+    if let Some(message) = argument_error(err) {
+        return Error::User(message);
+    }
+
+    if let Some(e) = err.downcast_ref::<reqwest::Error>() {
+        return Error::upstream("A service I rely on", e.to_string());
+    }
+
+    if let Some(e) = err.downcast_ref::<serenity::Error>() {
+        return match e {
+            serenity::Error::Model(ModelError::InvalidPermissions(p)) => {
+                Error::Permission(format!("I need the {:?} permission for that", p))
+            }
+            serenity::Error::Http(_) | serenity::Error::Gateway(_) => {
+                Error::upstream("Discord", e.to_string())
+            }
+            _ => Error::Internal(e.to_string()),
+        };
+    }
+
+    Error::Internal(err.to_string())
+}
+
+/// The errors of `Args::single` and the like, for the usual argument types
+fn argument_error(err: &(dyn StdError + Send + Sync + 'static)) -> Option<String> {
+    macro_rules! parse_error {
+        ($($t:ty),*) => {
+            $(
+                if let Some(e) = err.downcast_ref::<ArgError<$t>>() {
+                    return Some(match e {
+                        ArgError::Eos => String::from("Some arguments are missing, see the help of this command"),
+                        ArgError::Parse(why) => format!("An argument is not valid: {}", why),
+                        _ => String::from("The arguments are not valid, see the help of this command"),
+                    });
+                }
+            )*
+        };
+    }
+
+    parse_error!(Infallible, ParseIntError, ParseFloatError);
+    None
+}
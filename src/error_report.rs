@@ -0,0 +1,259 @@
+//! Grouped reports of the errors which are otherwise only in the logs
+//!
+//! Every error is recorded with `report`, grouped by where it happened and its message.
+//! A task sends the groups with new occurrences every `error_report.interval` seconds,
+//! so a failing service ends up as one line with a count instead of a message per failure
+
+use crate::config::ErrorReport;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use core::time::Duration;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serenity::builder::CreateEmbed;
+use serenity::http::Http;
+use serenity::model::id::UserId;
+use std::error::Error as StdError;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// How many characters of the trace are kept
+const TRACE_LIMIT: usize = 600;
+
+/// The groups not seen for this long are forgotten, so they count from zero again
+const FORGET_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often the config is read again while the reports are disabled
+const DISABLED_POLL: Duration = Duration::from_secs(60);
+
+/// Discord refuses the embeds with more characters than this, all their texts together
+const EMBED_LIMIT: usize = 6000;
+
+/// Or with more fields
+const MAX_FIELDS: usize = 25;
+
+#[derive(Debug, Clone)]
+struct Occurrences {
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    count: u64,
+    /// Since the last report
+    unreported: u64,
+    trace: String,
+}
+
+lazy_static! {
+    /// By `(source, message)`
+    static ref ERRORS: DashMap<(String, String), Occurrences> = DashMap::new();
+}
+
+/// Record an error of `source`, which is a command, an auto-command or a task
+pub fn report(source: impl Into<String>, err: &(dyn StdError + 'static)) {
+    let now = Utc::now();
+    let message = err.to_string().lines().next().unwrap_or_default().to_owned();
+
+    ERRORS
+        .entry((source.into(), message))
+        .and_modify(|v| {
+            v.last_seen = now;
+            v.count += 1;
+            v.unreported += 1;
+        })
+        .or_insert_with(|| Occurrences {
+            first_seen: now,
+            last_seen: now,
+            count: 1,
+            unreported: 1,
+            trace: trace(err),
+        });
+}
+
+/// The debug output of the error followed by its sources, trimmed
+fn trace(err: &(dyn StdError + 'static)) -> String {
+    let mut res = format!("{:?}", err);
+    let mut source = err.source();
+
+    while let Some(e) = source {
+        write!(res, "\ncaused by: {}", e).ok();
+        source = e.source();
+    }
+
+    trim(&res, TRACE_LIMIT)
+}
+
+fn trim(s: &str, limit: usize) -> String {
+    if s.chars().count() <= limit {
+        return s.to_owned();
+    }
+
+    let mut res: String = s.chars().take(limit).collect();
+    res.push('…');
+    res
+}
+
+/// Send the reports periodically, as configured in `error_report`
+pub fn spawn(http: Arc<Http>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let interval = crate::read_config().await.error_report.interval;
+
+            if interval == 0 {
+                ERRORS.clear();
+                tokio::time::sleep(DISABLED_POLL).await;
+                continue;
+            }
+
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            if let Err(why) = flush(&http).await {
+                error!("Cannot send the error report\n{:?}", why);
+            }
+        }
+    })
+}
+
+/// The groups with new occurrences, they stay pending until `mark_reported`
+fn pending() -> Vec<(String, String, Occurrences)> {
+    let now = Utc::now();
+    let forget = chrono::Duration::from_std(FORGET_AFTER).unwrap_or_else(|_| chrono::Duration::days(1));
+
+    ERRORS.retain(|_, v| v.unreported > 0 || now - v.last_seen < forget);
+
+    let mut pending: Vec<_> = ERRORS
+        .iter()
+        .filter(|v| v.unreported > 0)
+        .map(|v| {
+            let (source, message) = v.key().to_owned();
+            (source, message, v.value().clone())
+        })
+        .collect();
+
+    // The most frequent first, they are the most likely to be cut off otherwise
+    pending.sort_by(|a, b| b.2.unreported.cmp(&a.2.unreported));
+    pending
+}
+
+/// Once sent, the occurrences recorded meanwhile are left for the next report
+fn mark_reported(sent: &[(String, String, Occurrences)]) {
+    for (source, message, v) in sent {
+        if let Some(mut entry) = ERRORS.get_mut(&(source.to_owned(), message.to_owned())) {
+            entry.unreported = entry.unreported.saturating_sub(v.unreported);
+        }
+    }
+}
+
+async fn flush(http: &Arc<Http>) -> Result<()> {
+    let pending = pending();
+
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let (config, owners, theme, format) = {
+        let config = crate::read_config().await;
+        let report: ErrorReport = config.error_report.clone();
+
+        (report, config.owners.to_owned(), config.theme(None), config.time.format.to_string())
+    };
+
+    let total: u64 = pending.iter().map(|v| v.2.unreported).sum();
+    let hidden = pending.len().saturating_sub(config.max_entries);
+
+    let title = format!("{} errors in the last {} seconds", total, config.interval);
+    let footer = format!("And {} more, see the logs", hidden);
+
+    let fields = pending.iter().take(config.max_entries).map(|(source, message, v)| {
+        let name = trim(&format!("{} ×{} ({} in total)", source, v.unreported, v.count), 250);
+        let value = format!(
+            "First seen {}, last seen {}\n```{}```",
+            v.first_seen.format(&format),
+            v.last_seen.format(&format),
+            trim(&format!("{}\n{}", message, v.trace), 900),
+        );
+
+        (name, value)
+    });
+
+    // Split over as many embeds as needed, the footer is counted in each of them
+    let reserved = title.chars().count() + footer.chars().count();
+    let mut chunks = vec![Vec::new()];
+    let mut size = reserved;
+
+    for (name, value) in fields {
+        let len = name.chars().count() + value.chars().count();
+        let chunk = chunks.last_mut().expect("There is always a chunk");
+
+        if !chunk.is_empty() && (size + len > EMBED_LIMIT || chunk.len() == MAX_FIELDS) {
+            chunks.push(Vec::new());
+            size = reserved;
+        }
+
+        size += len;
+        chunks.last_mut().expect("There is always a chunk").push((name, value));
+    }
+
+    let count = chunks.len();
+    let embeds: Vec<CreateEmbed> = chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, fields)| {
+            let mut embed = theme.embed(|c| c.error);
+            embed.title(&title);
+            embed.timestamp(crate::utils::now());
+
+            for (name, value) in fields {
+                embed.field(name, value, false);
+            }
+
+            if hidden > 0 && i + 1 == count {
+                embed.footer(|f| f.text(&footer));
+            }
+
+            embed
+        })
+        .collect();
+
+    if let Some(channel) = config.channel {
+        for embed in embeds {
+            channel.send_message(http, |m| m.set_embed(embed)).await?;
+        }
+
+        mark_reported(&pending);
+        return Ok(());
+    }
+
+    let owners: Vec<UserId> = if owners.is_empty() {
+        vec![http.get_current_application_info().await?.owner.id]
+    } else {
+        owners.into_iter().collect()
+    };
+
+    let mut sent = false;
+
+    // An owner who cannot be messaged does not keep the report from the others
+    for owner in owners {
+        match send_dm(http, owner, &embeds).await {
+            Ok(()) => sent = true,
+            Err(why) => warn!("Cannot send the error report to {}\n{:?}", owner, why),
+        }
+    }
+
+    if !sent {
+        return Err("No owner could receive the error report".into());
+    }
+
+    mark_reported(&pending);
+    Ok(())
+}
+
+async fn send_dm(http: &Arc<Http>, owner: UserId, embeds: &[CreateEmbed]) -> Result<()> {
+    let dm = owner.create_dm_channel(http).await?;
+
+    for embed in embeds {
+        let embed = embed.clone();
+        dm.send_message(http, |m| m.set_embed(embed)).await?;
+    }
+
+    Ok(())
+}
@@ -5,14 +5,15 @@ use serenity::model::{
     event::{MessageUpdateEvent, ResumedEvent},
     gateway::{Activity, Ready},
     id::{ChannelId, GuildId, MessageId},
+    interactions::Interaction,
     user::OnlineStatus,
 };
 
 use crate::{
     cache::MessageCache,
-    storages::{CacheStorage, DatabaseKey, ReminderNotify},
+    storages::{CacheStorage, DatabaseKey},
     traits::ChannelExt,
-    types::Reminder,
+    types::{Reminder, ReminderTree},
     utils::*,
     Result,
 };
@@ -25,7 +26,8 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::time;
 
-static CURRENT_CHANNEL: AtomicU64 = AtomicU64::new(450521152272728065);
+/// Where the console messages go, 0 until a channel is known
+static CURRENT_CHANNEL: AtomicU64 = AtomicU64::new(0);
 static LOCKED: AtomicBool = AtomicBool::new(false);
 
 pub struct Handler {
@@ -131,13 +133,12 @@ impl EventHandler for Handler {
             None => to_say.push_str("\nBut I cannot remember how it was..."),
         };
 
-        let send_embed = log_channel
-            .send_embed(&ctx)
-            .with_description(to_say)
-            .with_current_timestamp()
-            .with_fields(fields)
-            .with_color(crate::read_config().await.color.message_update)
-            .await;
+        let mut embed = themed_embed(Some(guild_id), |c| c.message_update).await;
+        embed.description(to_say);
+        embed.timestamp(now());
+        embed.fields(fields);
+
+        let send_embed = log_channel.send_message(&ctx, |m| m.set_embed(embed)).await;
 
         if let Err(why) = send_embed {
             error!("Cannot send the message update log\n{:#?}", why);
@@ -158,6 +159,12 @@ impl EventHandler for Handler {
         process_deleted_message(&ctx, channel_id, msgs.into_iter().rev()).await
     }
 
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::ApplicationCommand(command) = interaction {
+            crate::slash::dispatch(&ctx, &command).await;
+        }
+    }
+
     async fn ready(&self, ctx: Context, _ready: Ready) {
         let mess = {
             let resume = self.resume.load(Ordering::SeqCst);
@@ -184,7 +191,34 @@ impl EventHandler for Handler {
             }
 
             if let Ok(info) = ctx.http.get_current_application_info().await {
-                crate::write_config().await.masters.insert(info.owner.id);
+                let mut config = crate::write_config().await;
+                config.app_owner = Some(info.owner.id);
+                config.masters.insert(info.owner.id);
+            }
+
+            if let Some(channel) = crate::read_config().await.console.channel {
+                // Only if no message has been received yet
+                let _ = CURRENT_CHANNEL.compare_exchange(0, channel.0, Ordering::SeqCst, Ordering::SeqCst);
+            }
+
+            tokio::spawn(crate::config_watcher::watch(Arc::clone(&ctx.http)));
+            crate::error_report::spawn(Arc::clone(&ctx.http));
+
+            let slash = {
+                let config = crate::read_config().await;
+
+                if config.slash.enable {
+                    Some(config.slash.guild)
+                } else {
+                    None
+                }
+            };
+
+            if let Some(guild) = slash {
+                match crate::slash::register(&ctx.http, guild).await {
+                    Ok(n) => info!("Registered {} slash commands", n),
+                    Err(why) => error!("Cannot register the slash commands\n{:?}", why),
+                }
             }
 
             let arc_ctx = Arc::new(ctx);
@@ -307,12 +341,12 @@ async fn _process_deleted(
         channel_id.0
     );
 
-    log_channel.send_embed(&ctx)
-        .with_description(content)
-        .with_fields(fields)
-        .with_color(crate::read_config().await.color.message_delete)
-        .with_current_timestamp()
-        .await?;
+    let mut embed = themed_embed(Some(guild_id), |c| c.message_delete).await;
+    embed.description(content);
+    embed.fields(fields);
+    embed.timestamp(now());
+
+    log_channel.send_message(&ctx, |m| m.set_embed(embed)).await?;
     
     if !msg.attachments.is_empty() {
         log_channel.send_message(ctx, |message| {
@@ -329,30 +363,44 @@ async fn _process_deleted(
 }
 
 async fn reminder(ctx: Arc<Context>) {
-    use tokio::sync::Notify;
+    use futures::stream::StreamExt;
 
-    let notify = Arc::new(Notify::new());
-    let mut data = ctx.data.write().await;
-    let db = data
-        .get::<DatabaseKey>()
-        .and_then(|db| db.open("Reminders").ok())
+    let db: ReminderTree = get_data::<DatabaseKey>(&ctx)
+        .await
+        .and_then(|db| reminder_tree(&db).ok())
         .unwrap();
 
-    data.insert::<ReminderNotify>(Arc::clone(&notify));
-    drop(data);
+    // Any change of the reminders may change the next one, so every change wakes the loop up
+    let mut changes = match db.watch() {
+        Ok(w) => w,
+        Err(why) => {
+            error!("Cannot watch the reminders, the reminder system is disabled\n{:?}", why);
+            return;
+        }
+    };
 
     loop {
-        let first_reminder = db.get_all::<i64, Reminder>().next();
+        // The keys are positive timestamps, so the first one is the earliest
+        let first_reminder = match db.first_async().await {
+            Ok(v) => v,
+            Err(why) => {
+                error!("Cannot get the next reminder {:?}", why);
+                crate::error_report::report("reminders", &*why);
+                None
+            }
+        };
+
         match first_reminder {
             Some((timestamp, value)) => {
                 let wait_time = timestamp - Utc::now().timestamp();
                 let duration = match wait_time.try_into() {
                     Ok(d) => Duration::from_secs(d),
                     Err(_) => {
-                        value.remind(&*ctx).await.ok();
+                        remind(&ctx, &value).await;
 
-                        if let Err(why) = db.remove(&timestamp) {
+                        if let Err(why) = db.remove_async(&timestamp).await {
                             error!("Error while removing the reminder {:?}", why);
+                            crate::error_report::report("reminders", &*why);
                         }
 
                         continue;
@@ -363,20 +411,34 @@ async fn reminder(ctx: Arc<Context>) {
 
                 tokio::select! {
                     _ = time::sleep(duration) => {
-                        value.remind(&*ctx).await.ok();
+                        remind(&ctx, &value).await;
 
-                        if let Err(why) = db.remove(&timestamp) {
+                        if let Err(why) = db.remove_async(&timestamp).await {
                             error!("Error while removing the reminder {:?}", why);
+                            crate::error_report::report("reminders", &*why);
                         }
                     }
 
-                    _= notify.notified() => {}
+                    change = changes.next() => if change.is_none() {
+                        break;
+                    }
                 }
             }
 
-            None => notify.notified().await,
+            None => if changes.next().await.is_none() {
+                break;
+            }
         }
     }
+
+    error!("The reminders watcher has stopped, the reminder system is disabled");
+}
+
+async fn remind(ctx: &Context, reminder: &Reminder) {
+    if let Err(why) = reminder.remind(ctx).await {
+        error!("Cannot send a reminder\n{:?}", why);
+        crate::error_report::report("reminders", &*why);
+    }
 }
 
 async fn read_input(ctx: Arc<Context>) {
@@ -411,7 +473,14 @@ async fn process_input<'a>(
 ) -> Result<()> {
     match input {
         Input::Message(s) => {
-            let channel = ChannelId(CURRENT_CHANNEL.load(Ordering::SeqCst));
+            let channel = match CURRENT_CHANNEL.load(Ordering::SeqCst) {
+                0 => {
+                    println!("No channel to send to yet, use `:lock {{channel_id}}` to choose one");
+                    return Ok(());
+                }
+                id => ChannelId(id),
+            };
+
             channel.broadcast_typing(&ctx).await?;
 
             let typing_time = if s.len() * 200 > 5000 {
@@ -4,18 +4,23 @@ use serenity::client::Context;
 use serenity::prelude::TypeMapKey;
 use serenity::framework::standard::macros::{help, hook};
 use serenity::framework::{
-    standard::{help_commands, Args, CommandGroup, CommandResult, Configuration, HelpOptions},
+    standard::{
+        buckets::LimitedFor, help_commands, Args, Command, CommandGroup, CommandResult,
+        Configuration, DispatchError, HelpOptions,
+    },
     Framework, StandardFramework,
 };
 use serenity::model::{
     channel::{Message, ReactionType},
-    id::{GuildId, MessageId, UserId},
+    id::{MessageId, UserId},
     misc::EmojiIdentifier,
 };
 
 use crate::{
     commands::*,
-    storages::{AIStore, InforKey, ReqwestClient},
+    config::{BucketScope, Config},
+    slash::Origin,
+    storages::{AIStore, DatabaseKey, InforKey, ReqwestClient},
     traits::ChannelExt,
     types::Ref,
     utils::*,
@@ -34,6 +39,7 @@ use requester::ehentai::EhentaiApi;
 use requester::saucenao::SauceNao;
 use smallstr::SmallString;
 use std::collections::HashSet;
+use std::ptr;
 use std::sync::Arc;
 
 use magic::traits::MagicBool as _;
@@ -79,26 +85,67 @@ async fn stolen_help(
     Ok(())
 }
 
-pub fn get_framework() -> impl Framework {
+/// Every command group, in the order they are registered
+static GROUPS: &[&CommandGroup] = &[
+    &MASTER_GROUP,
+    &GENERAL_GROUP,
+    &GUILDMASTER_GROUP,
+    &ADMINISTRATION_GROUP,
+    &GAME_GROUP,
+    &POKEMON_GROUP,
+    &OSU_GROUP,
+    &UTILITY_GROUP,
+    &IMAGE_GROUP,
+    &RGB_GROUP,
+    &TAGS_GROUP,
+];
+
+/// The functions run by `normal_message`, they can be toggled the same as the commands
+pub const AUTO_COMMANDS: &[&str] = &[
+    "mention_rgb",
+    "repeat_words",
+    "respect",
+    "eliza_response",
+    "rgb_tu",
+    "find_sauce",
+    "find_sadkaede",
+    "find_nhentai",
+];
+
+/// The owners can use the `owners_only` commands
+pub async fn get_framework(owners: HashSet<UserId>) -> impl Framework {
     let mut framework = StandardFramework::new()
-        // .bucket("basic", |b| b.delay(2).time_span(10).limit(3))
-        // .await
-        .group(&MASTER_GROUP)
-        .group(&GENERAL_GROUP)
-        .group(&GUILDMASTER_GROUP)
-        .group(&ADMINISTRATION_GROUP)
-        .group(&GAME_GROUP)
-        .group(&POKEMON_GROUP)
-        .group(&OSU_GROUP)
-        .group(&UTILITY_GROUP)
-        .group(&IMAGE_GROUP)
-        .group(&RGB_GROUP)
         .help(&STOLEN_HELP)
-        .configure(framwork_config)
+        .configure(|config| framwork_config(config, owners))
         .before(before_cmd)
         .after(after_cmd)
+        .on_dispatch_error(dispatch_error)
+        .unrecognised_command(unrecognised_command)
         .normal_message(normal_message);
 
+    let buckets = crate::read_config().await.buckets.clone();
+
+    for (name, bucket) in buckets {
+        let limited_for = match bucket.scope {
+            BucketScope::User => LimitedFor::User,
+            BucketScope::Channel => LimitedFor::Channel,
+            BucketScope::Guild => LimitedFor::Guild,
+        };
+
+        framework = framework
+            .bucket(&name, |b| {
+                b.limit_for(limited_for)
+                    .delay(bucket.delay)
+                    .time_span(bucket.time_span)
+                    .limit(bucket.limit)
+            })
+            .await;
+    }
+
+    for group in GROUPS {
+        framework = framework.group(group);
+    }
+
     //if has_external_command("ffmpeg") {
     //    framework.group_add(&MUSIC_GROUP);
     //}
@@ -106,11 +153,257 @@ pub fn get_framework() -> impl Framework {
     framework
 }
 
-fn framwork_config(config: &mut Configuration) -> &mut Configuration {
-    let mut owners = HashSet::new();
-    let mut disabled_commands = HashSet::new();
+/// Every command with the groups containing it, from the outermost one
+fn all_commands() -> Vec<(&'static Command, Vec<&'static CommandGroup>)> {
+    fn walk(
+        groups: &[&'static CommandGroup],
+        path: &mut Vec<&'static CommandGroup>,
+        out: &mut Vec<(&'static Command, Vec<&'static CommandGroup>)>,
+    ) {
+        for group in groups {
+            path.push(group);
+
+            for &command in group.options.commands {
+                out.push((command, path.clone()));
+            }
+
+            walk(group.options.sub_groups, path, out);
+            path.pop();
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(GROUPS, &mut Vec::new(), &mut out);
+    out
+}
+
+/// The name under which a command is toggled and permitted, its first name,
+/// qualified by its group (as `reminder.list`) when another command has the same name
+pub fn command_key(command: &'static Command) -> String {
+    let commands = all_commands();
+    let name = command.options.names[0];
+
+    let shared = commands
+        .iter()
+        .any(|(c, _)| !ptr::eq(*c, command) && c.options.names[0].eq_ignore_ascii_case(name));
+
+    let group = commands
+        .iter()
+        .find(|(c, _)| ptr::eq(*c, command))
+        .and_then(|(_, groups)| groups.last());
+
+    match group {
+        Some(group) if shared => format!("{}.{}", group.name, name).to_lowercase(),
+        _ => name.to_lowercase(),
+    }
+}
+
+/// The names deciding whether a command may run: its key then its groups, from the innermost one
+pub fn command_path(command: &'static Command) -> Vec<String> {
+    let groups = all_commands()
+        .into_iter()
+        .find(|(c, _)| ptr::eq(*c, command))
+        .map(|(_, groups)| groups)
+        .unwrap_or_default();
+
+    std::iter::once(command_key(command))
+        .chain(groups.iter().rev().map(|g| g.name.to_lowercase()))
+        .collect()
+}
+
+/// The same as `command_path` for what `toggle_name` returns,
+/// `None` if there is no such command, group or auto-command
+pub fn toggle_path(name: &str) -> Option<Vec<String>> {
+    fn find_group(groups: &[&'static CommandGroup], name: &str, path: &mut Vec<String>) -> bool {
+        for group in groups {
+            path.push(group.name.to_lowercase());
+
+            if group.name.eq_ignore_ascii_case(name)
+                || find_group(group.options.sub_groups, name, path)
+            {
+                return true;
+            }
+
+            path.pop();
+        }
+
+        false
+    }
+
+    if AUTO_COMMANDS.iter().any(|v| v.eq_ignore_ascii_case(name)) {
+        return Some(vec![name.to_lowercase()]);
+    }
+
+    let command = all_commands()
+        .into_iter()
+        .map(|(c, _)| c)
+        .find(|&c| command_key(c).eq_ignore_ascii_case(name));
+
+    if let Some(command) = command {
+        return Some(command_path(command));
+    }
+
+    let mut path = Vec::new();
+
+    if find_group(GROUPS, name, &mut path) {
+        path.reverse();
+        Some(path)
+    } else {
+        None
+    }
+}
 
-    owners.insert(UserId(239825449637642240));
+/// The name under which a command, a group or an auto-command is toggled and permitted
+/// A command is found by any of its aliases, by its key (see `command_key`),
+/// or by what is typed to run it, as `remind list`
+pub fn toggle_name(name: &str) -> std::result::Result<String, String> {
+    let name = name.trim();
+
+    if let Some(v) = AUTO_COMMANDS.iter().find(|v| v.eq_ignore_ascii_case(name)) {
+        return Ok(v.to_string());
+    }
+
+    let commands = all_commands();
+
+    let keyed = commands
+        .iter()
+        .find(|(c, _)| command_key(*c).eq_ignore_ascii_case(name));
+
+    if let Some((command, _)) = keyed {
+        return Ok(command_key(*command));
+    }
+
+    let words = name.split_whitespace().collect::<Vec<_>>();
+
+    if words.len() > 1 {
+        if let Some(command) = resolve(GROUPS, &words) {
+            return Ok(command_key(command));
+        }
+    }
+
+    let mut named = commands
+        .iter()
+        .filter(|(c, _)| c.options.names.iter().any(|v| v.eq_ignore_ascii_case(name)))
+        .map(|(c, _)| *c)
+        .collect::<Vec<_>>();
+
+    named.dedup_by(|a, b| ptr::eq(*a, *b));
+
+    match named.as_slice() {
+        [command] => return Ok(command_key(*command)),
+        [] => {}
+        commands => {
+            let keys = commands
+                .iter()
+                .map(|&c| format!("`{}`", command_key(c)))
+                .join(", ");
+
+            return Err(format!("Several commands are named `{}`, choose one of {}", name, keys));
+        }
+    }
+
+    toggle_path(name)
+        .and_then(|path| path.into_iter().next())
+        .ok_or_else(|| format!("There is no command, group or auto-command named `{}`", name))
+}
+
+/// The command run by the words typed after the prefix, the same way the framework finds it
+fn resolve(groups: &[&'static CommandGroup], words: &[&str]) -> Option<&'static Command> {
+    groups.iter().find_map(|group| {
+        let options = group.options;
+        let words = if options.prefixes.is_empty() {
+            words
+        } else {
+            let (first, rest) = words.split_first()?;
+
+            if !options.prefixes.iter().any(|v| v.eq_ignore_ascii_case(first)) {
+                return None;
+            }
+
+            rest
+        };
+
+        let named = words.first().and_then(|word| {
+            options
+                .commands
+                .iter()
+                .copied()
+                .find(|c| c.options.names.iter().any(|v| v.eq_ignore_ascii_case(word)))
+        });
+
+        resolve(options.sub_groups, words).or(named).or_else(|| {
+            if options.prefixes.is_empty() {
+                None
+            } else {
+                options.default_command
+            }
+        })
+    })
+}
+
+/// The command the framework dispatched for a message, the hooks are only given its name
+/// Falls back to the first command with this name if the message cannot be followed
+async fn dispatched(msg: &Message, cmd_name: &str) -> Option<&'static Command> {
+    let is_named = |c: &&'static Command| {
+        c.options.names.iter().any(|v| v.eq_ignore_ascii_case(cmd_name))
+    };
+
+    let found = prefixes(msg)
+        .await
+        .iter()
+        .filter_map(|prefix| msg.content.strip_prefix(prefix.as_str()))
+        .find_map(|content| {
+            let words = content.split_whitespace().collect::<Vec<_>>();
+            resolve(GROUPS, &words).filter(is_named)
+        });
+
+    found.or_else(|| all_commands().into_iter().map(|(c, _)| c).find(is_named))
+}
+
+/// Every prefix a message may start with, the guild one included
+pub async fn prefixes(msg: &Message) -> Vec<String> {
+    let config = crate::read_config().await;
+    let guild = msg
+        .guild_id
+        .and_then(|g| config.guilds.get(&g))
+        .and_then(|g| g.prefix.to_owned());
+
+    let mut prefixes = vec![config.prefix.to_string(), config.master_prefix.to_string()];
+    prefixes.extend(guild.map(|v| v.to_string()));
+    prefixes
+}
+
+/// Whether a word after the prefix already runs a command or names a group,
+/// a tag with this name could never run
+pub fn is_reserved(name: &str) -> bool {
+    fn find(groups: &[&'static CommandGroup], name: &str) -> bool {
+        groups.iter().any(|group| {
+            let options = group.options;
+
+            if options.prefixes.is_empty() {
+                options
+                    .commands
+                    .iter()
+                    .any(|c| c.options.names.iter().any(|v| v.eq_ignore_ascii_case(name)))
+                    || find(options.sub_groups, name)
+            } else {
+                options.prefixes.iter().any(|v| v.eq_ignore_ascii_case(name))
+            }
+        })
+    }
+
+    STOLEN_HELP.options.names.iter().any(|v| v.eq_ignore_ascii_case(name)) || find(GROUPS, name)
+}
+
+/// Whether the names (see `GuildConfig::is_enabled`) may run in the channel of the message
+fn is_enabled_in(config: &Config, msg: &Message, names: &[&str], default: bool) -> bool {
+    msg.guild_id
+        .and_then(|g| config.guilds.get(&g))
+        .map_or(default, |g| g.is_enabled(msg.channel_id.0, names, default))
+}
+
+fn framwork_config(config: &mut Configuration, owners: HashSet<UserId>) -> &mut Configuration {
+    let mut disabled_commands = HashSet::new();
 
     if !has_external_command("ffmpeg") {
         disabled_commands.insert(String::from("touhou_music_quiz"));
@@ -152,6 +445,91 @@ async fn master_prefix(_ctx: &Context, msg: &Message) -> Option<String> {
         .then(|| config.master_prefix.to_string())
 }
 
+#[hook]
+async fn dispatch_error(ctx: &Context, msg: &Message, error: DispatchError) {
+    if let DispatchError::Ratelimited(wait) = error {
+        slow_down(ctx, &Origin::from(msg), wait).await;
+    }
+}
+
+/// Tell the user of a rate limited command how long to wait
+pub async fn slow_down(ctx: &Context, origin: &Origin<'_>, wait: Duration) {
+    // Rounded up, "wait 0s" would not help anyone
+    let wait = Duration::from_secs(wait.as_secs() + u64::from(wait.subsec_nanos() > 0));
+    let mut embed = themed_embed(origin.guild_id(), |c| c.error).await;
+    embed
+        .title("Slow down!")
+        .description(format!(
+            "You are using this command too often, please wait **{}** before trying again",
+            humantime::format_duration(wait)
+        ));
+
+    origin.send_embed(ctx, embed).await.ok();
+}
+
+/// Why a command may not run
+pub enum Refusal {
+    /// Blacklisted or toggled off, the prefix commands stay silent about it
+    Disabled,
+    /// By a permission rule of the guild
+    Denied,
+}
+
+impl Refusal {
+    pub fn message(&self, cmd_name: &str) -> String {
+        match self {
+            Self::Disabled => format!("`{}` is disabled here", cmd_name),
+            Self::Denied => format!("You are not allowed to use `{}` here", cmd_name),
+        }
+    }
+}
+
+/// Check the blacklist, the toggles and the permission rules before running a command,
+/// from a message or a slash command
+/// `path` is the command followed by its groups, see `command_path`
+pub async fn refusal(
+    ctx: &Context,
+    origin: &Origin<'_>,
+    cmd_name: &str,
+    path: &[String],
+) -> Option<Refusal> {
+    let config = crate::read_config().await;
+
+    if config.cmd_blacklist.contains(&cmd_name.into()) {
+        return Some(Refusal::Disabled);
+    }
+
+    let names = path.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let guild = origin.guild_id().and_then(|g| config.guilds.get(&g));
+
+    if let Some(guild) = guild.as_ref() {
+        if !guild.is_enabled(origin.channel_id().0, &names, true) {
+            info!("The command {} is disabled in this channel", cmd_name);
+            return Some(Refusal::Disabled);
+        }
+    }
+
+    let rule = guild.and_then(|g| crate::guild_settings::rule_for(&g, origin, &names));
+
+    drop(config);
+
+    if let Some(rule) = rule.filter(|v| !v.allow) {
+        if !crate::guild_settings::is_exempt(ctx, origin).await {
+            info!(
+                "The command {} is denied to {} by the rule of {}",
+                cmd_name,
+                origin.author().tag(),
+                rule.target
+            );
+
+            return Some(Refusal::Denied);
+        }
+    }
+
+    None
+}
+
 #[hook]
 async fn before_cmd(ctx: &Context, msg: &Message, cmd_name: &str) -> bool {
     info!("Found command {}", cmd_name.bold().underlined());
@@ -160,12 +538,18 @@ async fn before_cmd(ctx: &Context, msg: &Message, cmd_name: &str) -> bool {
         typing(&ctx, msg.channel_id);
     }
 
-    if crate::read_config()
-        .await
-        .cmd_blacklist
-        .contains(&cmd_name.into())
-    {
-        return false;
+    let path = match dispatched(msg, cmd_name).await {
+        Some(command) => command_path(command),
+        None => vec![cmd_name.to_lowercase()],
+    };
+
+    match refusal(ctx, &Origin::from(msg), cmd_name, &path).await {
+        None => {}
+        Some(Refusal::Disabled) => return false,
+        Some(refusal) => {
+            msg.channel_id.say(ctx, refusal.message(cmd_name)).await.ok();
+            return false;
+        }
     }
 
     EXECUTION_LIST.insert(msg.id, Utc::now());
@@ -175,35 +559,76 @@ async fn before_cmd(ctx: &Context, msg: &Message, cmd_name: &str) -> bool {
 #[hook]
 async fn after_cmd(ctx: &Context, msg: &Message, cmd: &str, err: CommandResult) {
     let start_time = match EXECUTION_LIST.remove(&msg.id) {
-        Some((_, v)) => v.timestamp_millis(),
-        None => msg.timestamp.timestamp_millis(),
+        Some((_, v)) => v,
+        None => msg.timestamp,
     };
 
+    conclude(ctx, &Origin::from(msg), cmd, start_time, err).await;
+}
+
+/// Record the invocation of a command, and tell what went wrong if it failed
+pub async fn conclude(
+    ctx: &Context,
+    origin: &Origin<'_>,
+    cmd: &str,
+    start_time: DateTime<Utc>,
+    err: CommandResult,
+) {
+    let duration = (Utc::now() - start_time).num_milliseconds().max(0) as u64;
+    let invocation = crate::stats::Invocation {
+        command: cmd.to_owned(),
+        guild: origin.guild_id().map(|v| v.0),
+        user: origin.author().id.0,
+        duration,
+        success: err.is_ok(),
+    };
+
+    if let Some(db) = get_data::<DatabaseKey>(ctx).await {
+        if let Err(why) = crate::stats::record(&db, origin.id(), invocation).await {
+            error!("Cannot record the invocation of {}\n{:?}", cmd, why);
+        }
+    }
+
     match err {
         Ok(_) => {
-            let now = Utc::now().timestamp_millis();
             info!(
                 "Successfully executed the command {}, time passed {}ms",
                 cmd.cyan(),
-                now - start_time
+                duration
             );
         }
         Err(why) => {
-            error!("Couldn't execute the command {}\n{:#?}", cmd.magenta(), why);
-            let mess = format!("Cannot execute the command **__{}__**```{}```", cmd, why);
+            let error = crate::error::classify(&*why);
 
-            msg.channel_id
-                .send_embed(ctx)
-                .with_color(crate::read_config().await.color.error)
-                .with_description(mess)
-                .await
-                .ok();
+            if error.is_internal() {
+                error!("Couldn't execute the command {}\n{:#?}", cmd.magenta(), why);
+                crate::error_report::report(format!("command {}", cmd), &*why);
+            } else {
+                warn!("Couldn't execute the command {}\n{}", cmd.magenta(), why);
+            }
+
+            let mess = format!(
+                "Cannot execute the command **__{}__**\n{}",
+                cmd,
+                error.user_message()
+            );
+
+            let mut embed = themed_embed(origin.guild_id(), |c| c.error).await;
+            embed.description(mess);
+
+            origin.send_embed(ctx, embed).await.ok();
         }
     }
+}
 
-    if let Some(info) = ctx.data.read().await.get::<InforKey>() {
-        info.executed_one();
+/// The prefix matched but no command did, it may be a tag of the guild
+#[hook]
+async fn unrecognised_command(ctx: &Context, msg: &Message, name: &str) {
+    if msg.author.bot {
+        return;
     }
+
+    crate::tags::invoke(ctx, msg, name).await;
 }
 
 #[hook]
@@ -220,8 +645,9 @@ async fn normal_message(ctx: &Context, msg: &Message) {
         ( $( $x:ident ),* ) => {
             $(
                 let func = SmallString::from(stringify!($x));
+                let default = !config.disable_auto_cmd.contains(&func);
 
-                if !config.disable_auto_cmd.contains(&func) {
+                if is_enabled_in(&config, msg, &[stringify!($x)], default) {
                     futs.push($x(&ctx, &msg).boxed());
                     names.push(func);
                 }
@@ -229,6 +655,7 @@ async fn normal_message(ctx: &Context, msg: &Message) {
         };
     }
 
+    // Keep in sync with `AUTO_COMMANDS`
     exec_func! {
         mention_rgb,
         repeat_words,
@@ -246,7 +673,10 @@ async fn normal_message(ctx: &Context, msg: &Message) {
         .into_iter()
         .zip(names)
         .filter_map(|(func, name)| func.err().map(|e| (e, name)))
-        .for_each(|(err, name)| error!("Cannot exec the {} autocmd \n{:#?}", name, err));
+        .for_each(|(err, name)| {
+            error!("Cannot exec the {} autocmd \n{:#?}", name, err);
+            crate::error_report::report(format!("auto-command {}", name), &*err);
+        });
 }
 
 async fn mention_rgb(ctx: &Context, msg: &Message) -> Result<()> {
@@ -378,16 +808,16 @@ async fn eliza_response(ctx: &Context, msg: &Message) -> Result<()> {
 
 async fn rgb_tu(ctx: &Context, msg: &Message) -> Result<()> {
     use rand::prelude::*;
-    
-    if msg.author.id != UserId(314444746959355905) {
-        return Ok(())
-    }
 
     let config = crate::read_config().await;
     let rgb = match config.rgb.as_ref() {
         Some(r) => r,
         None => return Ok(()),
     };
+
+    if rgb.tu_user != Some(msg.author.id) {
+        return Ok(())
+    }
     
     if !matches!(msg.guild_id, Some(v) if rgb.tu_server.contains(&v)) {
         return Ok(())
@@ -561,7 +991,16 @@ async fn post_to_fb(
         return Ok(());
     }
 
-    if !matches!(msg.guild_id, Some(GuildId(418811018244784129))) {
+    let authors = {
+        let config = crate::read_config().await;
+
+        match config.apikeys.facebook.as_ref() {
+            Some(page) if page.guild.is_some() && page.guild == msg.guild_id => page.authors.clone(),
+            _ => return Ok(()),
+        }
+    };
+
+    if authors.is_empty() {
         return Ok(());
     }
 
@@ -573,10 +1012,11 @@ async fn post_to_fb(
     let timeout = Duration::from_secs(30);
 
     let author = match wait_for_reaction(ctx, msg, reaction, timeout).await? {
-        Some(UserId(239825449637642240)) => "tmokenc",
-        Some(UserId(353026384601284609)) => "myon",
-        Some(UserId(303146279884685314)) => "Kai",
-        _ => return Ok(()),
+        Some(user) => match authors.iter().find(|v| v.id == user) {
+            Some(author) => author.name.as_str(),
+            None => return Ok(()),
+        },
+        None => return Ok(()),
     };
 
     let (url, query) = {
@@ -605,40 +1045,39 @@ async fn post_to_fb(
 
     let content = format!("Posting to Loli Chronicle as **#{}**", author);
 
-    let mess = msg
-        .channel_id
-        .send_embed(ctx)
-        .with_description(&content)
-        .with_thumbnail(data.img_url())
-        .with_color(crate::read_config().await.color.information)
-        .with_current_timestamp();
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.description(&content);
+    embed.thumbnail(data.img_url());
+    embed.timestamp(now());
+
+    let mess = msg.channel_id.send_message(ctx, |m| m.set_embed(embed));
 
     let post = async { req.post(&url).query(&query).send().await?.text().await };
 
     let (mess, post) = future::join(mess, post).await;
-    let mut embed = serenity::builder::CreateEmbed::default();
-
-    embed.timestamp(now());
-    embed.thumbnail(data.img_url());
-    
     let text = post?;
     let post = serde_json::from_str::<PagePhotoPost>(&text);
 
-    match post {
+    let mut embed = match post {
         Ok(post) => {
+            let mut embed = themed_embed(msg.guild_id, |c| c.success).await;
             embed.description(format!("Successfully posted as **#{}**!!!", author));
-            embed.color(crate::read_config().await.color.success);
             embed.field("ID", &post.id, true);
             embed.field("Post ID", &post.post_id, true);
+            embed
         }
         
         Err(why) => {
             log::error!("Error while posting image to facebook\n{:#?}", text);
+            let mut embed = themed_embed(msg.guild_id, |c| c.error).await;
             embed.description(format!("Error while posting the image```{:#?}```", why));
-            embed.color(crate::read_config().await.color.error);
+            embed
         }
         
-    }
+    };
+
+    embed.timestamp(now());
+    embed.thumbnail(data.img_url());
 
     match mess {
         Ok(mess) => mess.channel_id
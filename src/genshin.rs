@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 use db::DbInstance;
+use crate::constants::GENSHIN_WATCH;
 use crate::traits::{Embedable, CreateEmbed, RawEventHandlerRef};
+use crate::types::GenshinWatchTree;
 use std::sync::atomic::{Ordering, AtomicBool};
 use std::fmt::{self, Write};
 use std::sync::Arc;
@@ -89,14 +91,14 @@ impl Embedable for RegionsEmbed {
 
 pub struct GenshinEvent {
     spawned: AtomicBool,
-    db: DbInstance,
+    db: GenshinWatchTree,
 }
 
 impl GenshinEvent {
     pub fn new(db: &DbInstance) -> crate::Result<Self> {
         Ok(Self {
             spawned: AtomicBool::default(),
-            db: db.open("genshin_watch")?,
+            db: db.open_tree(GENSHIN_WATCH)?,
         })
     }
 }
@@ -118,10 +120,10 @@ impl RawEventHandlerRef for GenshinEvent {
             }
             
             Event::MessageDelete(e) => {
-                let msg: Option<u64> = self.db.get(e.channel_id.as_u64()).ok().flatten();
+                let msg = self.db.get_async(e.channel_id.as_u64()).await.ok().flatten();
                 if let Some(msg) = msg {
                     if msg == e.message_id.0 {
-                        if let Err(why) = self.db.remove(e.channel_id.as_u64()) {
+                        if let Err(why) = self.db.remove_async(e.channel_id.as_u64()).await {
                             log::error!("Error while removing a genshin channel:\n{:#?}", why);
                         }
                     }
@@ -130,10 +132,10 @@ impl RawEventHandlerRef for GenshinEvent {
             }
             
             Event::MessageDeleteBulk(e) => {
-                let msg: Option<u64> = self.db.get(e.channel_id.as_u64()).ok().flatten();
+                let msg = self.db.get_async(e.channel_id.as_u64()).await.ok().flatten();
                 if let Some(msg) = msg {
                     if e.ids.iter().find(|v| v.0 == msg).is_some() {
-                        if let Err(why) = self.db.remove(e.channel_id.as_u64()) {
+                        if let Err(why) = self.db.remove_async(e.channel_id.as_u64()).await {
                             log::error!("Error while removing a genshin channel:\n{:#?}", why);
                         }
                     }
@@ -151,9 +153,18 @@ fn time_to_next_minute() -> StdDuration {
     StdDuration::from_secs((next - current) as u64)
 }
 
-async fn update_time(http: Arc<Http>, db: DbInstance) {
+async fn update_time(http: Arc<Http>, db: GenshinWatchTree) {
     loop {
-        for (channel, msg) in db.get_all::<u64, u64>() {
+        let watching = match db.get_all_async().await {
+            Ok(v) => v,
+            Err(why) => {
+                log::error!("Cannot read the genshin watching channels\n{:#?}", why);
+                crate::error_report::report("genshin timer", &*why);
+                Vec::new()
+            }
+        };
+
+        for (channel, msg) in watching {
             let http = Arc::clone(&http);
             tokio::spawn(async move {
                 let send = ChannelId(channel)
@@ -165,6 +176,7 @@ async fn update_time(http: Arc<Http>, db: DbInstance) {
                 
                 if let Err(why) = send {
                     log::error!("Error while editing genshin timer on channel {}\n{:#?}", channel, why);
+                    crate::error_report::report("genshin timer", &why);
                 }
             });
         }
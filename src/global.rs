@@ -3,5 +3,6 @@ use lazy_static::lazy_static;
 use tokio::sync::RwLock;
 
 lazy_static! {
+    /// Checked by `Instance::start` before its first use
     pub static ref CONFIG: RwLock<Config> = RwLock::new(Config::init().unwrap());
 }
@@ -0,0 +1,516 @@
+//! The registry of every per-guild setting, used by the `option` commands
+//! A field of `GuildConfig` is made configurable by adding it to `SETTINGS`
+
+use crate::types::{GuildConfig, SimpleRole};
+use crate::utils::{extract_channel_ids, now, themed_embed, update_guild_config};
+use crate::Result;
+use lazy_static::lazy_static;
+use magic::traits::MagicIter as _;
+use serenity::builder::CreateEmbed;
+use serenity::client::Context;
+use serenity::model::channel::Message;
+use serenity::model::id::{GuildId, RoleId};
+use serenity::model::permissions::Permissions;
+use serenity::utils::parse_role;
+use std::collections::HashSet;
+
+mod permissions;
+mod transfer;
+
+pub use permissions::{is_exempt, parse_targets, rule_for, set_rule};
+pub use transfer::{import, GuildExport, ImportReport, SettingChange};
+
+/// The type of a setting, which decides how its value is parsed and shown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Bool,
+    Text { max_len: usize },
+    Channel,
+    Channels,
+    /// Separated by `, `
+    Words,
+    Roles,
+    /// `#rrggbb` or `0xrrggbb`
+    Color,
+    Url,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Text(Option<String>),
+    Channel(Option<u64>),
+    Channels(HashSet<u64>),
+    Words(HashSet<String>),
+    Roles(Vec<SimpleRole>),
+    Color(Option<u64>),
+}
+
+pub struct Setting {
+    /// The dotted path of the field in `GuildConfig`
+    pub key: &'static str,
+    pub description: &'static str,
+    pub kind: Kind,
+    /// Needed to change the setting, reading it is open to everyone
+    pub permissions: Permissions,
+    get: fn(&GuildConfig) -> Value,
+    set: fn(&mut GuildConfig, Value),
+}
+
+macro_rules! setting {
+    ($key:literal, $kind:expr, $permissions:ident, $description:literal,
+     |$g:ident| $get:expr, |$sg:ident, $v:ident| $set:expr) => {
+        Setting {
+            key: $key,
+            description: $description,
+            kind: $kind,
+            permissions: Permissions::$permissions,
+            get: |$g| $get,
+            set: |$sg, $v| $set,
+        }
+    };
+}
+
+lazy_static! {
+    pub static ref SETTINGS: Vec<Setting> = vec![
+        setting!("prefix", Kind::Text { max_len: 8 }, MANAGE_GUILD,
+            "The command prefix in this server, instead of the default one",
+            |g| Value::Text(g.prefix.as_ref().map(|v| v.to_string())),
+            |g, v| if let Value::Text(v) = v { g.prefix = v.map(Into::into) }),
+
+        setting!("logger.enable", Kind::Bool, MANAGE_GUILD,
+            "Log the edited and deleted messages",
+            |g| Value::Bool(g.logger.enable),
+            |g, v| if let Value::Bool(v) = v { g.logger.enable = v }),
+
+        setting!("logger.channel", Kind::Channel, MANAGE_GUILD,
+            "Where the logs are sent",
+            |g| Value::Channel(g.logger.channel),
+            |g, v| if let Value::Channel(v) = v { g.logger.channel = v }),
+
+        setting!("find_sauce.enable", Kind::Bool, MANAGE_GUILD,
+            "Look for the source of the posted images",
+            |g| Value::Bool(g.find_sauce.enable),
+            |g, v| if let Value::Bool(v) = v { g.find_sauce.enable = v }),
+
+        setting!("find_sauce.all", Kind::Bool, MANAGE_GUILD,
+            "Look for the sources in every channel, instead of only in `find_sauce.channels`",
+            |g| Value::Bool(g.find_sauce.all),
+            |g, v| if let Value::Bool(v) = v { g.find_sauce.all = v }),
+
+        setting!("find_sauce.channels", Kind::Channels, MANAGE_GUILD,
+            "The channels to look for the sources in",
+            |g| Value::Channels(g.find_sauce.channels.clone()),
+            |g, v| if let Value::Channels(v) = v { g.find_sauce.channels = v }),
+
+        setting!("find_sadkaede.enable", Kind::Bool, MANAGE_GUILD,
+            "Show the SadKaede links posted in the chat",
+            |g| Value::Bool(g.find_sadkaede.enable),
+            |g, v| if let Value::Bool(v) = v { g.find_sadkaede.enable = v }),
+
+        setting!("find_sadkaede.all", Kind::Bool, MANAGE_GUILD,
+            "Show the SadKaede links in every channel, instead of only in `find_sadkaede.channels`",
+            |g| Value::Bool(g.find_sadkaede.all),
+            |g, v| if let Value::Bool(v) = v { g.find_sadkaede.all = v }),
+
+        setting!("find_sadkaede.channels", Kind::Channels, MANAGE_GUILD,
+            "The channels to show the SadKaede links in",
+            |g| Value::Channels(g.find_sadkaede.channels.clone()),
+            |g, v| if let Value::Channels(v) = v { g.find_sadkaede.channels = v }),
+
+        setting!("repeat_words.enable", Kind::Bool, MANAGE_GUILD,
+            "Repeat the words of `repeat_words.words` when they appear in the chat",
+            |g| Value::Bool(g.repeat_words.enable),
+            |g, v| if let Value::Bool(v) = v { g.repeat_words.enable = v }),
+
+        setting!("repeat_words.words", Kind::Words, MANAGE_GUILD,
+            "The words to be repeated",
+            |g| Value::Words(g.repeat_words.words.clone()),
+            |g, v| if let Value::Words(v) = v { g.repeat_words.words = v }),
+
+        setting!("rgblized", Kind::Roles, MANAGE_ROLES,
+            "The roles of the RGB commands",
+            |g| Value::Roles(g.rgblized.clone().unwrap_or_default()),
+            |g, v| if let Value::Roles(v) = v {
+                g.rgblized = None;
+
+                if !v.is_empty() {
+                    g.extend_rgb(v);
+                }
+            }),
+
+        setting!("theme.information", Kind::Color, MANAGE_GUILD,
+            "The color of the informative embeds",
+            |g| Value::Color(g.theme.information),
+            |g, v| if let Value::Color(v) = v { g.theme.information = v }),
+
+        setting!("theme.success", Kind::Color, MANAGE_GUILD,
+            "The color of the embeds telling that something went well",
+            |g| Value::Color(g.theme.success),
+            |g, v| if let Value::Color(v) = v { g.theme.success = v }),
+
+        setting!("theme.error", Kind::Color, MANAGE_GUILD,
+            "The color of the error embeds",
+            |g| Value::Color(g.theme.error),
+            |g, v| if let Value::Color(v) = v { g.theme.error = v }),
+
+        setting!("theme.message_update", Kind::Color, MANAGE_GUILD,
+            "The color of the logs of the edited messages",
+            |g| Value::Color(g.theme.message_update),
+            |g, v| if let Value::Color(v) = v { g.theme.message_update = v }),
+
+        setting!("theme.message_delete", Kind::Color, MANAGE_GUILD,
+            "The color of the logs of the deleted messages",
+            |g| Value::Color(g.theme.message_delete),
+            |g, v| if let Value::Color(v) = v { g.theme.message_delete = v }),
+
+        setting!("theme.lovely", Kind::Color, MANAGE_GUILD,
+            "The color of the lovely embeds",
+            |g| Value::Color(g.theme.lovely),
+            |g, v| if let Value::Color(v) = v { g.theme.lovely = v }),
+
+        setting!("theme.thumbnail", Kind::Url, MANAGE_GUILD,
+            "The thumbnail of the embeds which have none of their own",
+            |g| Value::Text(g.theme.thumbnail.clone()),
+            |g, v| if let Value::Text(v) = v { g.theme.thumbnail = v }),
+
+        setting!("theme.footer", Kind::Text { max_len: 256 }, MANAGE_GUILD,
+            "The footer of the embeds which have none of their own",
+            |g| Value::Text(g.theme.footer.clone()),
+            |g, v| if let Value::Text(v) = v { g.theme.footer = v }),
+    ];
+}
+
+#[inline]
+pub fn find(key: &str) -> Option<&'static Setting> {
+    SETTINGS.iter().find(|v| v.key.eq_ignore_ascii_case(key))
+}
+
+/// Find the setting to be changed by the author of the message
+/// Reply with the reason and return `None` when it cannot be
+pub async fn find_writable(ctx: &Context, msg: &Message, key: &str) -> Result<Option<&'static Setting>> {
+    let setting = match find(key) {
+        Some(s) => s,
+        None => {
+            let mess = format!("There is no option `{}`, use `option list` to see them all", key);
+            msg.channel_id.say(ctx, mess).await?;
+            return Ok(None);
+        }
+    };
+
+    if !setting.is_allowed(ctx, msg).await {
+        let mess = format!("You need the {:?} permission to change `{}`", setting.permissions, setting.key);
+        msg.channel_id.say(ctx, mess).await?;
+        return Ok(None);
+    }
+
+    Ok(Some(setting))
+}
+
+/// Change a setting of the guild and save it, return the new value
+/// `f` may be run more than once, see `update_guild_config`
+pub async fn update<F>(
+    ctx: &Context,
+    guild_id: GuildId,
+    setting: &'static Setting,
+    mut f: F,
+) -> Result<Value>
+where
+    F: FnMut(&mut Value) + Send + 'static,
+{
+    update_guild_config(ctx, guild_id, move |guild| {
+        let mut value = setting.get(guild);
+        f(&mut value);
+        setting.set(guild, value);
+        setting.get(guild)
+    })
+    .await
+}
+
+/// Show a setting with its value
+pub async fn send(ctx: &Context, msg: &Message, setting: &Setting, value: &Value) -> Result<()> {
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    setting.append_to(&mut embed, value);
+    embed.timestamp(now());
+
+    msg.channel_id.send_message(ctx, |m| m.set_embed(embed)).await?;
+
+    Ok(())
+}
+
+impl Setting {
+    #[inline]
+    pub fn get(&self, guild: &GuildConfig) -> Value {
+        (self.get)(guild)
+    }
+
+    #[inline]
+    pub fn set(&self, guild: &mut GuildConfig, value: Value) {
+        (self.set)(guild, value)
+    }
+
+    #[inline]
+    pub fn default_value(&self) -> Value {
+        (self.get)(&GuildConfig::default())
+    }
+
+    /// Whether the author of the message may change this setting
+    pub async fn is_allowed(&self, ctx: &Context, msg: &Message) -> bool {
+        if crate::read_config().await.masters.contains(&msg.author.id) {
+            return true;
+        }
+
+        let permissions = match msg.member(ctx).await {
+            Ok(member) => member.permissions(ctx).await.unwrap_or_else(|_| Permissions::empty()),
+            Err(_) => Permissions::empty(),
+        };
+
+        permissions.administrator() || permissions.contains(self.permissions)
+    }
+
+    pub fn append_to(&self, embed: &mut CreateEmbed, value: &Value) {
+        embed.title(format!("Option `{}`", self.key));
+        embed.description(self.description);
+        embed.field("Value", value.to_string(), false);
+        embed.field("Type", self.kind.name(), true);
+        embed.field("Default", self.default_value().to_string(), true);
+    }
+}
+
+impl Kind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bool => "on / off",
+            Self::Text { .. } => "text",
+            Self::Channel => "channel",
+            Self::Channels => "channels",
+            Self::Words => "words, separated by `, `",
+            Self::Roles => "roles",
+            Self::Color => "color, as `#rrggbb`",
+            Self::Url => "link",
+        }
+    }
+
+    /// Parse the input as a value of this kind, or explain why it cannot be
+    pub async fn parse(&self, ctx: &Context, msg: &Message, input: &str) -> Result<Value, String> {
+        let input = input.trim();
+
+        match self {
+            Self::Bool => match input.to_lowercase().as_str() {
+                "on" | "true" | "yes" | "enable" | "enabled" | "1" => Ok(Value::Bool(true)),
+                "off" | "false" | "no" | "disable" | "disabled" | "0" => Ok(Value::Bool(false)),
+                _ => Err(format!("`{}` is neither on nor off", input)),
+            },
+
+            Self::Text { max_len } => {
+                if input.is_empty() {
+                    Err(String::from("The text cannot be empty, use `reset` to unset it"))
+                } else if input.len() > *max_len {
+                    Err(format!("The text cannot be longer than {} bytes", max_len))
+                } else {
+                    Ok(Value::Text(Some(input.to_owned())))
+                }
+            }
+
+            Self::Channel => match parse_channels(ctx, msg, input).await?.as_slice() {
+                [channel] => Ok(Value::Channel(Some(*channel))),
+                _ => Err(String::from("Please mention exactly one channel")),
+            },
+
+            Self::Channels => {
+                let channels = parse_channels(ctx, msg, input).await?;
+
+                if channels.is_empty() {
+                    Err(String::from("Please mention some channels"))
+                } else {
+                    Ok(Value::Channels(channels.into_iter().collect()))
+                }
+            }
+
+            Self::Words => {
+                let words: HashSet<String> = input
+                    .split(", ")
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(String::from)
+                    .collect();
+
+                if words.is_empty() {
+                    Err(String::from("Please give some words, separated by `, `"))
+                } else {
+                    Ok(Value::Words(words))
+                }
+            }
+
+            Self::Roles => {
+                let mut roles = Vec::new();
+
+                for id in input.split_whitespace().filter_map(parse_role) {
+                    match RoleId(id).to_role_cached(ctx).await {
+                        Some(role) if role.mentionable => roles.push(SimpleRole::from(role)),
+                        Some(role) => return Err(format!("The role {} is not mentionable", role.name)),
+                        None => return Err(format!("There is no role <@&{}> in this server", id)),
+                    }
+                }
+
+                if roles.is_empty() {
+                    Err(String::from("Please mention some roles"))
+                } else {
+                    Ok(Value::Roles(roles))
+                }
+            }
+
+            Self::Color => {
+                let hex = input
+                    .trim_start_matches('#')
+                    .trim_start_matches("0x")
+                    .trim_start_matches("0X");
+
+                match u64::from_str_radix(hex, 16) {
+                    Ok(v) if hex.len() <= 6 => Ok(Value::Color(Some(v))),
+                    _ => Err(format!("`{}` is not a color like `#9966ff`", input)),
+                }
+            }
+
+            Self::Url => {
+                if input.starts_with("https://") || input.starts_with("http://") {
+                    Ok(Value::Text(Some(input.to_owned())))
+                } else {
+                    Err(format!("`{}` is not a link", input))
+                }
+            }
+        }
+    }
+}
+
+/// The mentioned channels, which must be in the server of the message
+async fn parse_channels(ctx: &Context, msg: &Message, input: &str) -> Result<Vec<u64>, String> {
+    let guild_id = msg.guild_id.ok_or("The options only exist in a server")?;
+    let channels = guild_id.channels(ctx).await.map_err(|e| e.to_string())?;
+    let mut res = Vec::new();
+
+    for channel in extract_channel_ids(input) {
+        if !channels.contains_key(&channel) {
+            return Err(format!("There is no channel <#{}> in this server", channel));
+        }
+
+        res.push(channel.0);
+    }
+
+    Ok(res)
+}
+
+impl Value {
+    pub fn is_collection(&self) -> bool {
+        matches!(self, Self::Channels(_) | Self::Words(_) | Self::Roles(_))
+    }
+
+    /// Add the items of `other` to a collection
+    pub fn extend(&mut self, other: Value) {
+        match (self, other) {
+            (Self::Channels(a), Self::Channels(b)) => a.extend(b),
+            (Self::Words(a), Self::Words(b)) => a.extend(b),
+            (Self::Roles(a), Self::Roles(b)) => {
+                let new: Vec<_> = b.into_iter().filter(|v| a.iter().all(|x| x.id != v.id)).collect();
+                a.extend(new);
+            }
+            _ => {}
+        }
+    }
+
+    /// Remove the items of `other` from a collection
+    pub fn subtract(&mut self, other: &Value) {
+        match (self, other) {
+            (Self::Channels(a), Self::Channels(b)) => a.retain(|v| !b.contains(v)),
+            (Self::Words(a), Self::Words(b)) => a.retain(|v| !b.contains(v)),
+            (Self::Roles(a), Self::Roles(b)) => a.retain(|v| b.iter().all(|x| x.id != v.id)),
+            _ => {}
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let none = "*none*";
+
+        match self {
+            Self::Bool(true) => f.write_str("on"),
+            Self::Bool(false) => f.write_str("off"),
+            Self::Text(Some(v)) => write!(f, "`{}`", v),
+            Self::Channel(Some(v)) => write!(f, "<#{}>", v),
+            Self::Color(Some(v)) => write!(f, "`#{:06x}`", v),
+            Self::Text(None) | Self::Channel(None) | Self::Color(None) => f.write_str(none),
+            Self::Channels(v) if v.is_empty() => f.write_str(none),
+            Self::Words(v) if v.is_empty() => f.write_str(none),
+            Self::Roles(v) if v.is_empty() => f.write_str(none),
+            Self::Channels(v) => f.write_str(&v.iter().map(|v| format!("<#{}>", v)).join(" ")),
+            Self::Words(v) => f.write_str(&v.iter().map(|v| format!("`{}`", v)).join(", ")),
+            Self::Roles(v) => f.write_str(&v.iter().map(|v| v.to_string()).join(" ")),
+        }
+    }
+}
+
+/// Turn a command, a command group or an auto-command on (`Some(true)`), off (`Some(false)`)
+/// or back to its default (`None`), in the mentioned channels or else in the whole guild
+pub async fn toggle(ctx: &Context, msg: &Message, name: &str, enable: Option<bool>) -> Result<()> {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let name = match crate::framework::toggle_name(name) {
+        Ok(v) => v,
+        Err(mess) => {
+            msg.channel_id.say(ctx, mess).await?;
+            return Ok(());
+        }
+    };
+
+    let is_protected = crate::framework::toggle_path(&name)
+        .map_or(false, |path| path.iter().any(|v| v == "guildmaster"));
+
+    if enable == Some(false) && is_protected {
+        msg.channel_id.say(ctx, "The server option commands cannot be disabled").await?;
+        return Ok(());
+    }
+
+    let channels = extract_channel_ids(&msg.content);
+
+    let levels: Vec<_> = if channels.is_empty() {
+        vec![None]
+    } else {
+        channels.iter().map(|v| Some(v.0)).collect()
+    };
+
+    let toggled = name.clone();
+    update_guild_config(ctx, guild_id, move |guild| {
+        for &level in &levels {
+            let toggles = guild.toggles_mut(level);
+
+            match enable {
+                Some(v) => toggles.set(&toggled, v),
+                None => {
+                    toggles.unset(&toggled);
+                }
+            }
+        }
+
+        guild.prune_toggles();
+    })
+    .await?;
+
+    let state = match enable {
+        Some(true) => "enabled",
+        Some(false) => "disabled",
+        None => "back to its default",
+    };
+
+    let place = if channels.is_empty() {
+        String::from("this server")
+    } else {
+        channels.iter().map(|v| format!("<#{}>", v.0)).join(", ")
+    };
+
+    msg.channel_id.say(ctx, format!("`{}` is now {} in {}", name, state, place)).await?;
+    Ok(())
+}
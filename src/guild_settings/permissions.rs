@@ -0,0 +1,131 @@
+//! The permission rules of a guild, which allow or deny the commands and the command groups
+//! to some users, roles or channels on top of the toggles
+//! They cannot give more than the permissions a command requires, only narrow it down,
+//! and they never apply to the administrators of the guild or to the masters of the bot
+
+use super::parse_channels;
+use crate::error::Error;
+use crate::slash::Origin;
+use crate::types::{GuildConfig, Invoker, PermissionRule, PermissionTarget};
+use crate::utils::update_guild_config;
+use crate::Result;
+use magic::traits::MagicIter as _;
+use serenity::client::Context;
+use serenity::model::channel::Message;
+
+/// Whether the author of the command is not bound by the permission rules
+pub async fn is_exempt(ctx: &Context, origin: &Origin<'_>) -> bool {
+    if crate::read_config().await.masters.contains(&origin.author().id) {
+        return true;
+    }
+
+    let guild_id = match origin.guild_id() {
+        Some(id) => id,
+        None => return false,
+    };
+
+    match guild_id.member(ctx, origin.author().id).await {
+        Ok(member) => member
+            .permissions(ctx)
+            .await
+            .map_or(false, |v| v.administrator()),
+        Err(_) => false,
+    }
+}
+
+/// The rule deciding whether the author of the command may use something in its channel,
+/// see `GuildConfig::permission`
+pub fn rule_for(guild: &GuildConfig, origin: &Origin<'_>, names: &[&str]) -> Option<PermissionRule> {
+    let roles = origin.roles();
+    let invoker = Invoker {
+        user: origin.author().id.0,
+        roles: &roles,
+        channel: origin.channel_id().0,
+    };
+
+    guild.permission(names, &invoker).cloned()
+}
+
+/// The users, roles and channels mentioned in the message, and everyone if asked for
+pub async fn parse_targets(ctx: &Context, msg: &Message, args: &str) -> Result<Vec<PermissionTarget>> {
+    let mut targets = Vec::new();
+
+    targets.extend(msg.mentions.iter().map(|v| PermissionTarget::User(v.id.0)));
+    targets.extend(msg.mention_roles.iter().map(|v| PermissionTarget::Role(v.0)));
+
+    let channels = parse_channels(ctx, msg, args).await.map_err(Error::user)?;
+    targets.extend(channels.into_iter().map(PermissionTarget::Channel));
+
+    let everyone = msg.mention_everyone
+        || args
+            .split_whitespace()
+            .any(|v| v.eq_ignore_ascii_case("everyone") || v == "@everyone");
+
+    if everyone {
+        targets.push(PermissionTarget::Everyone);
+    }
+
+    targets.sort();
+    targets.dedup();
+
+    if targets.is_empty() {
+        return Err(Error::user("Mention the users, roles or channels, or say `everyone`").into());
+    }
+
+    Ok(targets)
+}
+
+/// Allow (`Some(true)`) or deny (`Some(false)`) a command or a command group to the targets,
+/// or remove their rules with `None`
+pub async fn set_rule(
+    ctx: &Context,
+    msg: &Message,
+    name: &str,
+    targets: &[PermissionTarget],
+    allow: Option<bool>,
+) -> Result<()> {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let name = crate::framework::toggle_name(name).map_err(Error::not_found)?;
+
+    if crate::framework::AUTO_COMMANDS.contains(&name.as_str()) {
+        let mess = "The auto-commands have no permission rules, use `enable` and `disable` for them";
+        return Err(Error::user(mess).into());
+    }
+
+    let rule_name = name.clone();
+    let rule_targets = targets.to_vec();
+    let removed = update_guild_config(ctx, guild_id, move |guild| {
+        let mut removed = 0;
+
+        for &target in &rule_targets {
+            match allow {
+                Some(v) => guild.set_permission(&rule_name, target, v),
+                None => removed += usize::from(guild.unset_permission(&rule_name, target)),
+            }
+        }
+
+        removed
+    })
+    .await?;
+
+    let who = targets.iter().map(|v| v.to_string()).join(", ");
+    let mess = match allow {
+        Some(true) => format!("`{}` is now allowed to {}", name, who),
+        Some(false) => format!("`{}` is now denied to {}", name, who),
+        None if removed == 0 => format!("There was no rule for `{}` and {}", name, who),
+        None => format!("Removed {} rules for `{}`", removed, name),
+    };
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.content(mess);
+            m.allowed_mentions(|a| a.empty_parse())
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,318 @@
+//! Moving the settings of a guild to a JSON file and back,
+//! possibly into another guild where the channels and roles have other IDs
+
+use super::SETTINGS;
+use crate::types::{GuildConfig, PermissionTarget, SimpleRole};
+use crate::utils::{stored_guild_config, update_guild_config};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serenity::client::Context;
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, GuildId, RoleId};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The content of an exported file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GuildExport {
+    /// The guild the settings were exported from
+    pub guild: u64,
+    /// The names of the channels used in the settings, to find them again in another guild
+    /// The roles already carry their name
+    pub channels: HashMap<u64, String>,
+    /// The names of the roles of the permission rules, the RGB roles carry their own
+    #[serde(default)]
+    pub roles: HashMap<u64, String>,
+    pub config: GuildConfig,
+}
+
+#[derive(Debug)]
+pub struct SettingChange {
+    pub key: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// What an import changes, or would change with a dry run
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub changes: Vec<SettingChange>,
+    /// The channels and roles without a match in the guild, they are left out
+    pub missing: Vec<String>,
+    /// The settings the author is not allowed to change, they are left untouched
+    pub denied: Vec<&'static str>,
+}
+
+fn channel_ids(config: &GuildConfig) -> HashSet<u64> {
+    config
+        .logger
+        .channel
+        .iter()
+        .chain(&config.find_sauce.channels)
+        .chain(&config.find_sadkaede.channels)
+        .chain(config.channel_toggles.keys())
+        .copied()
+        .chain(config.permissions.iter().filter_map(|v| match v.target {
+            PermissionTarget::Channel(id) => Some(id),
+            _ => None,
+        }))
+        .collect()
+}
+
+fn role_ids(config: &GuildConfig) -> HashSet<u64> {
+    config
+        .permissions
+        .iter()
+        .filter_map(|v| match v.target {
+            PermissionTarget::Role(id) => Some(id),
+            _ => None,
+        })
+        .collect()
+}
+
+fn permissions_summary(config: &GuildConfig) -> String {
+    let names = config.permissions.iter().map(|v| &v.name).collect::<HashSet<_>>();
+    format!(
+        "{} rules on {} commands or groups",
+        config.permissions.len(),
+        names.len()
+    )
+}
+
+fn toggles_summary(config: &GuildConfig) -> String {
+    format!(
+        "{} enabled, {} disabled in the server, {} channels with toggles",
+        config.toggles.enabled.len(),
+        config.toggles.disabled.len(),
+        config.channel_toggles.len()
+    )
+}
+
+impl GuildExport {
+    /// Export the settings of a guild, the default ones if it has never been configured
+    pub async fn new(ctx: &Context, guild_id: GuildId) -> Result<Self> {
+        let config = crate::read_config()
+            .await
+            .guilds
+            .get(&guild_id)
+            .map(|v| v.clone())
+            .unwrap_or_else(|| GuildConfig::new(guild_id));
+
+        let used = channel_ids(&config);
+        let channels = guild_id
+            .channels(ctx)
+            .await?
+            .into_iter()
+            .filter(|(id, _)| used.contains(&id.0))
+            .map(|(id, channel)| (id.0, channel.name))
+            .collect();
+
+        let used = role_ids(&config);
+        let roles = guild_id
+            .roles(ctx)
+            .await?
+            .into_iter()
+            .filter(|(id, _)| used.contains(&id.0))
+            .map(|(id, role)| (id.0, role.name))
+            .collect();
+
+        Ok(Self {
+            guild: guild_id.0,
+            channels,
+            roles,
+            config,
+        })
+    }
+
+    /// Turn the exported settings into ones of the guild
+    /// A channel or role is kept if it is in the guild, otherwise it is looked up by name
+    /// Return the names of those not found either way
+    pub async fn remap(self, ctx: &Context, guild_id: GuildId) -> Result<(GuildConfig, Vec<String>)> {
+        let channels = guild_id.channels(ctx).await?;
+        let roles = guild_id.roles(ctx).await?;
+        let names = self.channels;
+        let role_names = self.roles;
+        let mut missing = Vec::new();
+
+        let mut map_channel = |id: u64| -> Option<u64> {
+            if channels.contains_key(&ChannelId(id)) {
+                return Some(id);
+            }
+
+            let name = names.get(&id);
+            let found = name
+                .and_then(|name| channels.values().find(|c| &c.name == name))
+                .map(|c| c.id.0);
+
+            if found.is_none() {
+                missing.push(match name {
+                    Some(name) => format!("#{}", name),
+                    None => format!("<#{}>", id),
+                });
+            }
+
+            found
+        };
+
+        let mut config = self.config;
+        config.id = guild_id.0;
+        config.logger.channel = config.logger.channel.and_then(&mut map_channel);
+
+        config.find_sauce.channels = config
+            .find_sauce
+            .channels
+            .into_iter()
+            .filter_map(&mut map_channel)
+            .collect();
+
+        config.find_sadkaede.channels = config
+            .find_sadkaede
+            .channels
+            .into_iter()
+            .filter_map(&mut map_channel)
+            .collect();
+
+        config.channel_toggles = config
+            .channel_toggles
+            .into_iter()
+            .filter_map(|(id, toggles)| map_channel(id).map(|id| (id, toggles)))
+            .collect();
+
+        let mut lost_roles = Vec::new();
+        let permissions = std::mem::take(&mut config.permissions);
+        config.permissions = permissions
+            .into_iter()
+            .filter_map(|mut rule| {
+                rule.target = match rule.target {
+                    PermissionTarget::Channel(id) => PermissionTarget::Channel(map_channel(id)?),
+                    PermissionTarget::Role(id) => {
+                        let name = role_names.get(&id);
+                        let found = roles
+                            .get(&RoleId(id))
+                            .or_else(|| name.and_then(|name| roles.values().find(|r| &r.name == name)));
+
+                        match found {
+                            Some(role) => PermissionTarget::Role(role.id.0),
+                            None => {
+                                lost_roles.push(match name {
+                                    Some(name) => format!("@{}", name),
+                                    None => format!("<@&{}>", id),
+                                });
+
+                                return None;
+                            }
+                        }
+                    }
+
+                    // The users and everyone are the same in every guild
+                    target => target,
+                };
+
+                Some(rule)
+            })
+            .collect();
+
+        missing.extend(lost_roles);
+
+        config.rgblized = config
+            .rgblized
+            .map(|old| {
+                old.into_iter()
+                    .filter_map(|role| {
+                        let found = roles
+                            .get(&RoleId(role.id))
+                            .or_else(|| roles.values().find(|r| r.name == role.name.as_str()));
+
+                        if found.is_none() {
+                            missing.push(format!("@{}", role.name));
+                        }
+
+                        found.cloned().map(SimpleRole::from)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|v| !v.is_empty());
+
+        Ok((config, missing))
+    }
+}
+
+/// Copy the imported settings into the guild, only those the author of the message may change
+/// Nothing is saved with `dry_run`
+pub async fn import(
+    ctx: &Context,
+    msg: &Message,
+    guild_id: GuildId,
+    imported: GuildConfig,
+    dry_run: bool,
+) -> Result<ImportReport> {
+    let mut allowed = Vec::with_capacity(SETTINGS.len());
+
+    for setting in SETTINGS.iter() {
+        allowed.push(setting.is_allowed(ctx, msg).await);
+    }
+
+    if dry_run {
+        let mut current = stored_guild_config(ctx, guild_id).await?;
+        return Ok(apply_import(&mut current, &imported, &allowed));
+    }
+
+    update_guild_config(ctx, guild_id, move |guild| apply_import(guild, &imported, &allowed)).await
+}
+
+/// Copy the allowed settings of `imported` into `guild`, and report the changes
+fn apply_import(guild: &mut GuildConfig, imported: &GuildConfig, allowed: &[bool]) -> ImportReport {
+    let mut report = ImportReport::default();
+    let current = guild.clone();
+
+    for (setting, &allowed) in SETTINGS.iter().zip(allowed) {
+        let old = setting.get(&current);
+        let new = setting.get(imported);
+
+        if old == new {
+            continue;
+        }
+
+        if !allowed {
+            report.denied.push(setting.key);
+            continue;
+        }
+
+        report.changes.push(SettingChange {
+            key: setting.key,
+            old: old.to_string(),
+            new: new.to_string(),
+        });
+
+        setting.set(guild, new);
+    }
+
+    if current.toggles != imported.toggles || current.channel_toggles != imported.channel_toggles {
+        report.changes.push(SettingChange {
+            key: "toggles",
+            old: toggles_summary(&current),
+            new: toggles_summary(imported),
+        });
+
+        guild.toggles = imported.toggles.clone();
+        guild.channel_toggles = imported.channel_toggles.clone();
+    }
+
+    if current.permissions != imported.permissions {
+        report.changes.push(SettingChange {
+            key: "permissions",
+            old: permissions_summary(&current),
+            new: permissions_summary(imported),
+        });
+
+        guild.permissions = imported.permissions.clone();
+    }
+
+    report
+}
+
+impl fmt::Display for SettingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {} → {}", self.key, self.old, self.new)
+    }
+}
@@ -9,24 +9,38 @@ extern crate async_fs as fs;
 mod cache;
 mod commands;
 mod config;
+mod config_watcher;
 mod constants;
+mod error;
+mod error_report;
 mod events;
 mod framework;
 mod global;
+mod guild_settings;
 mod storages;
 mod traits;
 mod types;
 mod logger;
+mod migrations;
+mod slash;
+mod snapshot;
+mod stats;
+mod tags;
 mod utils;
 mod genshin;
 
+/// Boxed so `?` works with any error, the commands box an `error::Error` for the failures
+/// they understand, it is recovered by downcasting
 pub type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
 
+pub use migrations::schemas;
 pub use requester::*;
 pub use serenity::framework::standard::macros::hook;
 
+use std::collections::HashSet;
 use std::error::Error;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::config::Config;
 use crate::logger::EventLogger;
@@ -40,7 +54,9 @@ use eliza::Eliza;
 use futures::future;
 use magic::dark_magic::has_external_command;
 use serenity::client::bridge::gateway::{GatewayIntents, ShardManager};
-use serenity::model::id::GuildId;
+use serenity::http::Http;
+use serenity::model::prelude::CurrentApplicationInfo;
+use serenity::model::id::{GuildId, UserId};
 use serenity::Client;
 use songbird::serenity::SongbirdKey;
 use tokio::runtime::Handle as TokioHandle;
@@ -67,16 +83,21 @@ impl Drop for Instance {
 
 impl Instance {
     pub async fn start_with_db(token: &str, db: DbInstance) -> Result<Self> {
+        load_config()?;
+
         let rt = TokioHandle::try_current()?;
         let handler = Handler::new();
         let raw_handler = tomo_serenity_ext::MultiRawHandler::new();
         let raw_handler_clone = raw_handler.clone();
-        let framework = framework::get_framework();
+        let application = Http::new_with_token(token).get_current_application_info().await?;
+        let owners = owners(&application).await;
+        let framework = framework::get_framework(owners.clone()).await;
 
         raw_handler.add("Logger", EventLogger::new()).await;
         raw_handler.add("Genshin", genshin::GenshinEvent::new(&db)?).await;
 
         let mut client = Client::builder(token)
+            .application_id(application.id.0)
             .framework(framework)
             .event_handler(handler)
             .raw_event_handler(raw_handler_clone)
@@ -87,6 +108,24 @@ impl Instance {
             let (mut data, config) = future::join(client.data.write(), read_config()).await;
 
             let req = Reqwest::new();
+            migrate_db(&db).await?;
+            // A broken snapshot must not keep the bot from starting, the data is left as it was
+            let restore = snapshot::restore_pending(&db, &config.snapshot.dir, config.snapshot.keep);
+
+            if let Err(why) = restore.await {
+                error!("Cannot restore the scheduled snapshot\n{:?}", why);
+            }
+
+            db.spawn_sweeper(constants::SWEEP_INTERVAL);
+
+            stats::spawn_pruner(db.clone(), constants::SWEEP_INTERVAL);
+
+            if config.snapshot.interval > 0 {
+                snapshot::spawn(db.clone(), Duration::from_secs(config.snapshot.interval));
+            }
+
+            // Watch first so nothing written in between is missed
+            sync_guild_config_from_db(&db)?;
             fetch_guild_config_from_db(&db).await?;
             if let Err(why) = commands::pokemon::update_pokemon(&db, &req).await {
                 error!("\n{}", why);
@@ -98,6 +137,7 @@ impl Instance {
             data.insert::<ReqwestClient>(Arc::new(req));
             data.insert::<CacheStorage>(Arc::new(MyCache::new(config.temp_dir.as_ref())?));
             data.insert::<AIStore>(mutex_data(Eliza::from_file(&config.eliza_brain).unwrap()));
+            data.insert::<OwnersKey>(Arc::new(owners));
 
             if has_external_command("ffmpeg") {
                 data.insert::<SongbirdKey>(songbird::Songbird::serenity());
@@ -117,6 +157,8 @@ impl Instance {
     }
 
     pub async fn start(token: &str) -> Result<Self> {
+        load_config()?;
+
         let db = db::get_db_instance(&read_config().await.database.path, None)
             .await
             .ok_or("Cannot get the DbInstance")?;
@@ -144,6 +186,21 @@ fn mutex_data<T>(data: T) -> Arc<Mutex<T>> {
     Arc::new(Mutex::new(data))
 }
 
+/// Load the config with every problem reported at once,
+/// instead of panicking on the first use of `global::CONFIG`
+fn load_config() -> Result<()> {
+    let config = Config::init()?;
+
+    if let Err(why) = config.validate() {
+        error!("Cannot start with the current config\n{}", why);
+        return Err(why);
+    }
+
+    drop(config);
+    lazy_static::initialize(&global::CONFIG);
+    Ok(())
+}
+
 #[inline]
 async fn read_config() -> tokio::sync::RwLockReadGuard<'static, Config> {
     global::CONFIG.read().await
@@ -154,8 +211,38 @@ async fn write_config() -> tokio::sync::RwLockWriteGuard<'static, Config> {
     global::CONFIG.write().await
 }
 
+/// The owners from the config, or else the owner of the application
+async fn owners(application: &CurrentApplicationInfo) -> HashSet<UserId> {
+    let owners = read_config().await.owners.clone();
+
+    if !owners.is_empty() {
+        return owners;
+    }
+
+    info!("No owner in the config, using the owner of the application {}", application.owner.tag());
+    std::iter::once(application.owner.id).collect()
+}
+
+async fn migrate_db(db: &DbInstance) -> Result<()> {
+    let db = db.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        migrations::remove_legacy_keys(&db)?;
+        migrations::schemas().run(&db)
+    })
+    .await??;
+
+    if report.quarantined() > 0 {
+        warn!("Migrated the database with quarantined records\n{}", report);
+    } else if !report.is_empty() {
+        info!("Migrated the database\n{}", report);
+    }
+
+    Ok(())
+}
+
 async fn fetch_guild_config_from_db(db: &DbInstance) -> Result<()> {
-    let data = db.open("GuildConfig")?.get_all::<u64, GuildConfig>();
+    let tree: GuildConfigTree = db.open_tree(constants::GUILD_CONFIG)?;
+    let data = tree.get_all_async().await?;
     let guilds_config = &crate::read_config().await.guilds;
 
     for (k, v) in data {
@@ -165,6 +252,37 @@ async fn fetch_guild_config_from_db(db: &DbInstance) -> Result<()> {
     Ok(())
 }
 
+/// Keep the guild configs in memory up to date with every later write to the database,
+/// including the ones made by `import_db`
+fn sync_guild_config_from_db(db: &DbInstance) -> Result<()> {
+    use db::Event;
+    use futures::stream::StreamExt;
+
+    let tree: GuildConfigTree = db.open_tree(constants::GUILD_CONFIG)?;
+    let mut changes = tree.watch()?;
+
+    tokio::spawn(async move {
+        while let Some(event) = changes.next().await {
+            let config = read_config().await;
+
+            // A command may hold an entry of the map across an await
+            tokio::task::block_in_place(|| match event {
+                Event::Insert(k, v) => {
+                    config.guilds.insert(GuildId(k), v);
+                }
+
+                Event::Remove(k) => {
+                    config.guilds.remove(&GuildId(k));
+                }
+            });
+        }
+
+        error!("The guild config watcher has stopped");
+    });
+
+    Ok(())
+}
+
 #[inline]
 fn intents() -> GatewayIntents {
     GatewayIntents::all()
@@ -0,0 +1,268 @@
+//! Schema versions of the stored records
+//! Bincode is not self-describing, adding or changing a field of a stored type
+//! makes every record written before it unreadable, so any change to these types
+//! needs a version bump here and a `migrate` step from a frozen copy of the old layout
+
+use crate::commands::PokeKey;
+use crate::constants::*;
+use crate::stats::{Invocation, StatsKey};
+use crate::tags::{Tag, TagKey};
+use crate::types::{GuildConfig, Reminder};
+use crate::Result;
+use db::{DbInstance, Migrations, Schema};
+
+pub const GUILD_CONFIG_VERSION: u32 = 4;
+pub const REMINDERS_VERSION: u32 = 1;
+pub const GENSHIN_WATCH_VERSION: u32 = 1;
+pub const SMOGON_VERSION: u32 = 1;
+pub const SMOGON_DESCRIPTION_VERSION: u32 = 2;
+pub const COMMAND_STATS_VERSION: u32 = 1;
+pub const TAGS_VERSION: u32 = 1;
+
+pub fn schemas() -> Migrations {
+    Migrations::new()
+        .register(
+            Schema::new::<u64, GuildConfig>(GUILD_CONFIG, GUILD_CONFIG_VERSION)
+                // v2: the command toggles
+                .migrate(1, |old: v1::GuildConfig| old.upgrade())
+                // v3: the theme
+                .migrate(2, |old: v2::GuildConfig| old.upgrade())
+                // v4: the permission rules
+                .migrate(3, |old: v3::GuildConfig| old.upgrade()),
+        )
+        .register(Schema::new::<i64, Reminder>(REMINDERS, REMINDERS_VERSION))
+        .register(Schema::new::<u64, u64>(GENSHIN_WATCH, GENSHIN_WATCH_VERSION))
+        .register(Schema::keyed::<PokeKey>(SMOGON_POKEMON, SMOGON_VERSION))
+        .register(
+            Schema::keyed::<PokeKey>(SMOGON_DESCRIPTION, SMOGON_DESCRIPTION_VERSION)
+                // v2: the descriptions became a TTL cache, the old ones are fetched again
+                .discard(1),
+        )
+        .register(Schema::new::<StatsKey, Invocation>(COMMAND_STATS, COMMAND_STATS_VERSION))
+        .register(Schema::new::<TagKey, Tag>(TAGS, TAGS_VERSION))
+}
+
+/// Remove the keys of the root tree left by an older layout
+/// `c-time` was the fetch time of the corona cache before it became a TTL entry,
+/// the cache written along with it has the old layout and cannot be read anymore
+pub fn remove_legacy_keys(db: &DbInstance) -> Result<()> {
+    if db.get::<_, u64>(&"c-time")?.is_some() {
+        db.remove_many(vec!["c-time", "corona"])?;
+    }
+
+    Ok(())
+}
+
+/// The frozen layouts of the old versions
+/// Every type a version stores is copied here, never imported from `crate::types`,
+/// so a later change to the live types cannot change how the old records are read
+/// A version module re-exports the types it keeps unchanged from the previous one
+mod v1 {
+    use serde::{Deserialize, Serialize};
+    use smallstr::SmallString;
+    use std::collections::HashSet;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct GuildConfig {
+        pub id: u64,
+        pub prefix: Option<SmallString<[u8; 8]>>,
+        pub rgblized: Option<Vec<SimpleRole>>,
+        pub logger: DiscordLogger,
+        pub find_sauce: FindSauce,
+        pub find_sadkaede: FindSadKaede,
+        pub repeat_words: RepeatWords,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct SimpleRole {
+        pub name: SmallString<[u8; 32]>,
+        pub id: u64,
+        pub color: (u8, u8, u8),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct DiscordLogger {
+        pub enable: bool,
+        pub channel: Option<u64>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct FindSauce {
+        pub all: bool,
+        pub enable: bool,
+        pub channels: HashSet<u64>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct FindSadKaede {
+        pub all: bool,
+        pub enable: bool,
+        pub channels: HashSet<u64>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct RepeatWords {
+        pub enable: bool,
+        pub words: HashSet<String>,
+    }
+
+    impl GuildConfig {
+        pub fn upgrade(self) -> super::v2::GuildConfig {
+            super::v2::GuildConfig {
+                id: self.id,
+                prefix: self.prefix,
+                rgblized: self.rgblized,
+                logger: self.logger,
+                find_sauce: self.find_sauce,
+                find_sadkaede: self.find_sadkaede,
+                repeat_words: self.repeat_words,
+                toggles: super::v2::Toggles::default(),
+                channel_toggles: Default::default(),
+            }
+        }
+    }
+}
+
+mod v2 {
+    pub use super::v1::{DiscordLogger, FindSadKaede, FindSauce, RepeatWords, SimpleRole};
+    use serde::{Deserialize, Serialize};
+    use smallstr::SmallString;
+    use std::collections::{HashMap, HashSet};
+
+    #[derive(Serialize, Deserialize)]
+    pub struct GuildConfig {
+        pub id: u64,
+        pub prefix: Option<SmallString<[u8; 8]>>,
+        pub rgblized: Option<Vec<SimpleRole>>,
+        pub logger: DiscordLogger,
+        pub find_sauce: FindSauce,
+        pub find_sadkaede: FindSadKaede,
+        pub repeat_words: RepeatWords,
+        pub toggles: Toggles,
+        pub channel_toggles: HashMap<u64, Toggles>,
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct Toggles {
+        pub enabled: HashSet<String>,
+        pub disabled: HashSet<String>,
+    }
+
+    impl GuildConfig {
+        pub fn upgrade(self) -> super::v3::GuildConfig {
+            super::v3::GuildConfig {
+                id: self.id,
+                prefix: self.prefix,
+                rgblized: self.rgblized,
+                logger: self.logger,
+                find_sauce: self.find_sauce,
+                find_sadkaede: self.find_sadkaede,
+                repeat_words: self.repeat_words,
+                toggles: self.toggles,
+                channel_toggles: self.channel_toggles,
+                theme: super::v3::GuildTheme::default(),
+            }
+        }
+    }
+}
+
+mod v3 {
+    pub use super::v2::{DiscordLogger, FindSadKaede, FindSauce, RepeatWords, SimpleRole, Toggles};
+    use crate::types;
+    use serde::{Deserialize, Serialize};
+    use smallstr::SmallString;
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct GuildConfig {
+        pub id: u64,
+        pub prefix: Option<SmallString<[u8; 8]>>,
+        pub rgblized: Option<Vec<SimpleRole>>,
+        pub logger: DiscordLogger,
+        pub find_sauce: FindSauce,
+        pub find_sadkaede: FindSadKaede,
+        pub repeat_words: RepeatWords,
+        pub toggles: Toggles,
+        pub channel_toggles: HashMap<u64, Toggles>,
+        pub theme: GuildTheme,
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    pub struct GuildTheme {
+        pub information: Option<u64>,
+        pub success: Option<u64>,
+        pub error: Option<u64>,
+        pub message_update: Option<u64>,
+        pub message_delete: Option<u64>,
+        pub lovely: Option<u64>,
+        pub thumbnail: Option<String>,
+        pub footer: Option<String>,
+    }
+
+    impl GuildConfig {
+        /// The last frozen version, converted field by field into the live types
+        pub fn upgrade(self) -> types::GuildConfig {
+            types::GuildConfig {
+                id: self.id,
+                prefix: self.prefix,
+                rgblized: self
+                    .rgblized
+                    .map(|roles| roles.into_iter().map(role).collect()),
+                logger: types::DiscordLogger {
+                    enable: self.logger.enable,
+                    channel: self.logger.channel,
+                },
+                find_sauce: types::FindSauce {
+                    all: self.find_sauce.all,
+                    enable: self.find_sauce.enable,
+                    channels: self.find_sauce.channels,
+                },
+                find_sadkaede: types::FindSadKaede {
+                    all: self.find_sadkaede.all,
+                    enable: self.find_sadkaede.enable,
+                    channels: self.find_sadkaede.channels,
+                },
+                repeat_words: types::RepeatWords {
+                    enable: self.repeat_words.enable,
+                    words: self.repeat_words.words,
+                },
+                toggles: toggles(self.toggles),
+                channel_toggles: self
+                    .channel_toggles
+                    .into_iter()
+                    .map(|(k, v)| (k, toggles(v)))
+                    .collect(),
+                theme: theme(self.theme),
+                ..Default::default()
+            }
+        }
+    }
+
+    fn role(old: SimpleRole) -> types::SimpleRole {
+        types::SimpleRole {
+            name: old.name,
+            id: old.id,
+            color: old.color,
+        }
+    }
+
+    fn toggles(old: Toggles) -> types::Toggles {
+        types::Toggles {
+            enabled: old.enabled,
+            disabled: old.disabled,
+        }
+    }
+
+    fn theme(old: GuildTheme) -> types::GuildTheme {
+        types::GuildTheme {
+            information: old.information,
+            success: old.success,
+            error: old.error,
+            message_update: old.message_update,
+            message_delete: old.message_delete,
+            lovely: old.lovely,
+            thumbnail: old.thumbnail,
+            footer: old.footer,
+        }
+    }
+}
@@ -0,0 +1,509 @@
+//! The slash commands, the application commands of Discord
+//!
+//! A few commands are also registered as slash commands with typed options.
+//! Each `SlashCommand` points to its prefix command, whose name and description are registered,
+//! and both call the same function with an `Origin`, which answers the message or the interaction.
+//! The toggles, the permission rules, the buckets and the stats apply the same.
+//! The framework only rate limits the messages, the slash commands count their uses here
+
+use crate::commands::{
+    GENERAL_SLASH_COMMANDS, IMAGE_SLASH_COMMANDS, POKEMON_SLASH_COMMANDS, UTILITY_SLASH_COMMANDS,
+};
+use crate::config::BucketScope;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use core::time::Duration;
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use serenity::builder::{CreateApplicationCommands, CreateEmbed};
+use serenity::client::Context;
+use serenity::framework::standard::{Command, CommandResult};
+use serenity::http::Http;
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, GuildId, MessageId};
+use serenity::model::interactions::application_command::{
+    ApplicationCommand, ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+    ApplicationCommandInteractionDataOptionValue as OptionValue, ApplicationCommandOptionType,
+};
+use serenity::model::interactions::InteractionResponseType;
+use serenity::model::user::User;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Every slash command, by group
+static SLASH_COMMANDS: &[&[&SlashCommand]] = &[
+    GENERAL_SLASH_COMMANDS,
+    UTILITY_SLASH_COMMANDS,
+    IMAGE_SLASH_COMMANDS,
+    POKEMON_SLASH_COMMANDS,
+];
+
+/// Discord refuses longer descriptions
+const MAX_DESCRIPTION: usize = 100;
+
+lazy_static! {
+    /// The uses of each bucket, by bucket and by user, channel or guild
+    static ref BUCKET_USES: DashMap<(&'static str, u64), Uses> = DashMap::new();
+}
+
+/// The uses of a bucket by one user, channel or guild
+struct Uses {
+    /// `None` before the first use
+    last: Option<Instant>,
+    /// When the current `time_span` started
+    window: Instant,
+    count: u32,
+}
+
+pub type SlashFn =
+    for<'a> fn(&'a Context, &'a Origin<'a>, &'a SlashArgs) -> BoxFuture<'a, CommandResult>;
+
+pub struct SlashCommand {
+    /// The prefix command, for the name and the description
+    pub command: &'static Command,
+    /// Instead of the first name of the command, for the commands of a group
+    pub name: Option<&'static str>,
+    pub options: &'static [SlashOption],
+    pub run: SlashFn,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum OptionKind {
+    String,
+    Integer,
+    User,
+}
+
+#[derive(Debug)]
+pub struct SlashOption {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: OptionKind,
+    pub required: bool,
+}
+
+impl SlashCommand {
+    pub fn name(&self) -> &'static str {
+        self.name.unwrap_or(self.command.options.names[0])
+    }
+
+    /// The first line of the documentation of the command
+    fn description(&self) -> String {
+        let desc = self
+            .command
+            .options
+            .desc
+            .and_then(|v| v.lines().next())
+            .filter(|v| !v.is_empty())
+            .unwrap_or("No description");
+
+        desc.chars().take(MAX_DESCRIPTION).collect()
+    }
+}
+
+/// The options given to a slash command, by name
+#[derive(Debug, Default)]
+pub struct SlashArgs(HashMap<String, OptionValue>);
+
+impl SlashArgs {
+    fn new(options: &[ApplicationCommandInteractionDataOption]) -> Self {
+        let options = options
+            .iter()
+            .filter_map(|v| Some((v.name.to_owned(), v.resolved.to_owned()?)))
+            .collect();
+
+        Self(options)
+    }
+
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.0.get(name) {
+            Some(OptionValue::String(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn integer(&self, name: &str) -> Option<i64> {
+        match self.0.get(name) {
+            Some(OptionValue::Integer(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn user(&self, name: &str) -> Option<&User> {
+        match self.0.get(name) {
+            Some(OptionValue::User(v, _)) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+enum Source<'a> {
+    Message(&'a Message),
+    Slash(&'a ApplicationCommandInteraction),
+}
+
+/// Where a command comes from, a message or a slash command, and where it answers
+pub struct Origin<'a> {
+    source: Source<'a>,
+    created: DateTime<Utc>,
+    /// The first answer to a slash command, 0 until there is one
+    first_answer: AtomicU64,
+}
+
+impl<'a> From<&'a Message> for Origin<'a> {
+    fn from(msg: &'a Message) -> Self {
+        Self {
+            source: Source::Message(msg),
+            created: msg.timestamp,
+            first_answer: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<'a> From<&'a ApplicationCommandInteraction> for Origin<'a> {
+    fn from(interaction: &'a ApplicationCommandInteraction) -> Self {
+        Self {
+            source: Source::Slash(interaction),
+            created: Utc::now(),
+            first_answer: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Origin<'_> {
+    /// The id of the message or of the interaction
+    pub fn id(&self) -> u64 {
+        match self.source {
+            Source::Message(msg) => msg.id.0,
+            Source::Slash(interaction) => interaction.id.0,
+        }
+    }
+
+    pub fn channel_id(&self) -> ChannelId {
+        match self.source {
+            Source::Message(msg) => msg.channel_id,
+            Source::Slash(interaction) => interaction.channel_id,
+        }
+    }
+
+    pub fn guild_id(&self) -> Option<GuildId> {
+        match self.source {
+            Source::Message(msg) => msg.guild_id,
+            Source::Slash(interaction) => interaction.guild_id,
+        }
+    }
+
+    pub fn author(&self) -> &User {
+        match self.source {
+            Source::Message(msg) => &msg.author,
+            Source::Slash(interaction) => &interaction.user,
+        }
+    }
+
+    /// The roles of the author, empty outside of a guild
+    pub fn roles(&self) -> Vec<u64> {
+        let roles = match self.source {
+            Source::Message(msg) => msg.member.as_ref().map(|v| &v.roles),
+            Source::Slash(interaction) => interaction.member.as_ref().map(|v| &v.roles),
+        };
+
+        roles.map(|v| v.iter().map(|r| r.0).collect()).unwrap_or_default()
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    /// The message which invoked the command, `None` for a slash command
+    pub fn message(&self) -> Option<&Message> {
+        match self.source {
+            Source::Message(msg) => Some(msg),
+            Source::Slash(_) => None,
+        }
+    }
+
+    pub fn is_slash(&self) -> bool {
+        matches!(self.source, Source::Slash(_))
+    }
+
+    /// The message to link back to, the invoking one or else the answer
+    pub fn link_id(&self, answer: &Message) -> MessageId {
+        self.message().map_or(answer.id, |v| v.id)
+    }
+
+    pub async fn say(&self, ctx: &Context, content: impl Display) -> Result<Message> {
+        self.answer(ctx, Some(content.to_string()), None).await
+    }
+
+    pub async fn send_embed(&self, ctx: &Context, embed: CreateEmbed) -> Result<Message> {
+        self.answer(ctx, None, Some(embed)).await
+    }
+
+    async fn answer(
+        &self,
+        ctx: &Context,
+        content: Option<String>,
+        embed: Option<CreateEmbed>,
+    ) -> Result<Message> {
+        let interaction = match self.source {
+            Source::Message(msg) => {
+                let message = msg
+                    .channel_id
+                    .send_message(ctx, |m| {
+                        if let Some(content) = content {
+                            m.content(content);
+                        }
+
+                        if let Some(embed) = embed {
+                            m.set_embed(embed);
+                        }
+
+                        m
+                    })
+                    .await?;
+
+                return Ok(message);
+            }
+            Source::Slash(v) => v,
+        };
+
+        // The interaction has been deferred, its first answer replaces the "thinking" one
+        let message = if self.first_answer.load(Ordering::SeqCst) == 0 {
+            interaction
+                .edit_original_interaction_response(&ctx.http, |r| {
+                    if let Some(content) = content {
+                        r.content(content);
+                    }
+
+                    if let Some(embed) = embed {
+                        r.add_embed(embed);
+                    }
+
+                    r
+                })
+                .await?
+        } else {
+            interaction
+                .create_followup_message(&ctx.http, |r| {
+                    if let Some(content) = content {
+                        r.content(content);
+                    }
+
+                    if let Some(embed) = embed {
+                        r.add_embed(embed);
+                    }
+
+                    r
+                })
+                .await?
+        };
+
+        self.first_answer
+            .compare_exchange(0, message.id.0, Ordering::SeqCst, Ordering::SeqCst)
+            .ok();
+
+        Ok(message)
+    }
+
+    /// Replace the embed of a message sent with `send_embed`
+    pub async fn edit_embed(
+        &self,
+        ctx: &Context,
+        message: &mut Message,
+        embed: CreateEmbed,
+    ) -> Result<()> {
+        let interaction = match self.source {
+            Source::Message(_) => {
+                message
+                    .edit(ctx, |m| {
+                        m.embed(|e| {
+                            e.0 = embed.0;
+                            e
+                        })
+                    })
+                    .await?;
+
+                return Ok(());
+            }
+            Source::Slash(v) => v,
+        };
+
+        *message = if self.first_answer.load(Ordering::SeqCst) == message.id.0 {
+            interaction
+                .edit_original_interaction_response(&ctx.http, |r| r.add_embed(embed))
+                .await?
+        } else {
+            interaction
+                .edit_followup_message(&ctx.http, message.id, |r| r.add_embed(embed))
+                .await?
+        };
+
+        Ok(())
+    }
+
+    /// Let the rest of the answer be sent as normal messages to the channel,
+    /// for the paginators and the like
+    /// A slash command not answered yet would wait forever, its pending answer is removed
+    pub async fn hand_over(&self, ctx: &Context) -> Result<()> {
+        if let Source::Slash(interaction) = self.source {
+            let pending = self
+                .first_answer
+                .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok();
+
+            if pending {
+                interaction.delete_original_interaction_response(&ctx.http).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn find(name: &str) -> Option<&'static SlashCommand> {
+    SLASH_COMMANDS
+        .iter()
+        .flat_map(|v| v.iter())
+        .copied()
+        .find(|v| v.name() == name)
+}
+
+/// Register every slash command, in one guild with `guild` as they show up at once there,
+/// globally otherwise, the previously registered commands which are gone are removed
+pub async fn register(http: impl AsRef<Http>, guild: Option<GuildId>) -> Result<usize> {
+    let registered = match guild {
+        Some(guild) => guild.set_application_commands(http, create_commands).await?,
+        None => ApplicationCommand::set_global_application_commands(http, create_commands).await?,
+    };
+
+    Ok(registered.len())
+}
+
+fn create_commands(builder: &mut CreateApplicationCommands) -> &mut CreateApplicationCommands {
+    for command in SLASH_COMMANDS.iter().flat_map(|v| v.iter()) {
+        builder.create_application_command(|c| {
+            c.name(command.name());
+            c.description(command.description());
+
+            for option in command.options {
+                let kind = match option.kind {
+                    OptionKind::String => ApplicationCommandOptionType::String,
+                    OptionKind::Integer => ApplicationCommandOptionType::Integer,
+                    OptionKind::User => ApplicationCommandOptionType::User,
+                };
+
+                c.create_option(|o| {
+                    o.name(option.name)
+                        .description(option.description)
+                        .kind(kind)
+                        .required(option.required)
+                });
+            }
+
+            c
+        });
+    }
+
+    builder
+}
+
+/// Run a slash command, as the framework does with the prefix commands
+pub async fn dispatch(ctx: &Context, interaction: &ApplicationCommandInteraction) {
+    let command = match find(&interaction.data.name) {
+        Some(v) => v,
+        None => {
+            warn!("Got the unknown slash command {}", interaction.data.name);
+            return;
+        }
+    };
+
+    // Discord only waits 3 seconds for the first answer
+    let deferred = interaction
+        .create_interaction_response(&ctx.http, |r| {
+            r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+        })
+        .await;
+
+    if let Err(why) = deferred {
+        error!("Cannot answer the slash command {}\n{:?}", command.name(), why);
+        return;
+    }
+
+    let origin = Origin::from(interaction);
+    let name = command.command.options.names[0];
+
+    info!("Found slash command {}", command.name());
+
+    // The groups of the command it shares, `/reminder` follows the rules of `reminder`
+    let path = crate::framework::command_path(command.command);
+
+    if let Some(refusal) = crate::framework::refusal(ctx, &origin, name, &path).await {
+        origin.say(ctx, refusal.message(name)).await.ok();
+        return;
+    }
+
+    if let Some(wait) = ratelimit(command, &origin).await {
+        crate::framework::slow_down(ctx, &origin, wait).await;
+        origin.hand_over(ctx).await.ok();
+        return;
+    }
+
+    let args = SlashArgs::new(&interaction.data.options);
+    let res = (command.run)(ctx, &origin, &args).await;
+
+    crate::framework::conclude(ctx, &origin, name, origin.timestamp(), res).await;
+
+    // A command may finish without a word, the interaction would be "thinking" forever
+    if let Err(why) = origin.hand_over(ctx).await {
+        error!("Cannot clear the answer of the slash command {}\n{:?}", command.name(), why);
+    }
+}
+
+/// How long to wait before using the command again, `None` when this use is counted
+async fn ratelimit(command: &SlashCommand, origin: &Origin<'_>) -> Option<Duration> {
+    let name = command.command.options.bucket?;
+    let bucket = crate::read_config().await.buckets.get(name).cloned()?;
+
+    let target = match bucket.scope {
+        BucketScope::User => origin.author().id.0,
+        BucketScope::Channel => origin.channel_id().0,
+        BucketScope::Guild => origin.guild_id().map_or(origin.channel_id().0, |v| v.0),
+    };
+
+    let now = Instant::now();
+    let mut uses = BUCKET_USES.entry((name, target)).or_insert(Uses {
+        last: None,
+        window: now,
+        count: 0,
+    });
+
+    // Like the buckets of the framework, a `delay` between two uses and a `limit` every `time_span`
+    if let Some(last) = uses.last {
+        let delay = Duration::from_secs(bucket.delay);
+        let elapsed = now - last;
+
+        if elapsed < delay {
+            return Some(delay - elapsed);
+        }
+    }
+
+    if bucket.time_span > 0 {
+        let time_span = Duration::from_secs(bucket.time_span);
+        let window = now - uses.window;
+
+        if window >= time_span {
+            uses.window = now;
+            uses.count = 0;
+        } else if uses.count >= bucket.limit {
+            return Some(time_span - window);
+        }
+    }
+
+    uses.last = Some(now);
+    uses.count += 1;
+
+    None
+}
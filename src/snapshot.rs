@@ -0,0 +1,214 @@
+//! Periodic exports of the database, so it is never the only copy of the data
+//!
+//! Every snapshot is an `Archive` saved as `tomodb_{unix millis}.json` in the snapshot directory,
+//! the same format as the `export_db` command and the `tomodb` binary
+//! Restoring one replaces a running database behind the back of every command,
+//! so it is only scheduled here, and done on the next startup
+
+use crate::Result;
+use core::time::Duration;
+use db::{Archive, DbInstance};
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::task::{self, JoinHandle};
+
+const PREFIX: &str = "tomodb_";
+const EXTENSION: &str = ".json";
+
+/// Holds the file name of the snapshot to restore on the next startup
+const PENDING_RESTORE: &str = "restore";
+
+#[derive(Debug, Clone)]
+pub struct SnapshotFile {
+    pub path: PathBuf,
+    /// Unix timestamp in milliseconds
+    pub created_at: u64,
+    /// In bytes
+    pub size: u64,
+}
+
+impl SnapshotFile {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|v| v.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// A new `tomodb_{unix millis}.json` path in `dir`, with its time
+/// The file is created empty to reserve the name, a name already taken
+/// (by another snapshot or export in the same millisecond) moves to the next millisecond
+pub fn reserve_path(dir: &Path) -> Result<(PathBuf, u64)> {
+    let mut millis = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+
+    loop {
+        let path = dir.join(format!("{}{}{}", PREFIX, millis, EXTENSION));
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return Ok((path, millis)),
+            Err(why) if why.kind() == ErrorKind::AlreadyExists => millis += 1,
+            Err(why) => return Err(why.into()),
+        }
+    }
+}
+
+/// Export the whole database to a new snapshot, then remove the oldest ones over `keep`
+pub async fn take(db: &DbInstance, dir: &Path, keep: usize) -> Result<SnapshotFile> {
+    let db = db.clone();
+    let dir = dir.to_path_buf();
+
+    task::spawn_blocking(move || -> Result<SnapshotFile> {
+        std::fs::create_dir_all(&dir)?;
+
+        let (path, created_at) = reserve_path(&dir)?;
+
+        // Written aside first, a crash while writing must not leave a broken snapshot
+        let tmp = path.with_extension("tmp");
+        let written = Archive::export(&db, &crate::schemas(), |_| true)
+            .and_then(|archive| Ok(std::fs::write(&tmp, serde_json::to_vec(&archive)?)?))
+            .and_then(|_| Ok(std::fs::rename(&tmp, &path)?));
+
+        if let Err(why) = written {
+            std::fs::remove_file(&tmp).ok();
+            std::fs::remove_file(&path).ok();
+            return Err(why);
+        }
+
+        rotate(&dir, keep)?;
+
+        Ok(SnapshotFile {
+            size: std::fs::metadata(&path)?.len(),
+            created_at,
+            path,
+        })
+    })
+    .await?
+}
+
+/// Every snapshot in `dir`, the newest first
+pub async fn list(dir: &Path) -> Result<Vec<SnapshotFile>> {
+    let dir = dir.to_path_buf();
+    task::spawn_blocking(move || list_blocking(&dir)).await?
+}
+
+fn list_blocking(dir: &Path) -> Result<Vec<SnapshotFile>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut res = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        let created_at = name
+            .strip_prefix(PREFIX)
+            .and_then(|v| v.strip_suffix(EXTENSION))
+            .and_then(|v| v.parse::<u64>().ok())
+            // Named in seconds before, no millisecond timestamp is that small
+            .map(|v| if v < 1_000_000_000_000 { v * 1000 } else { v });
+
+        if let Some(created_at) = created_at {
+            res.push(SnapshotFile {
+                path: entry.path(),
+                created_at,
+                size: entry.metadata()?.len(),
+            });
+        }
+    }
+
+    res.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(res)
+}
+
+fn rotate(dir: &Path, keep: usize) -> Result<()> {
+    for old in list_blocking(dir)?.into_iter().skip(keep.max(1)) {
+        info!("Removing the old snapshot {}", old.name());
+        std::fs::remove_file(&old.path)?;
+    }
+
+    Ok(())
+}
+
+/// Take a snapshot every `every`, with the directory and rotation read from the config each time
+pub fn spawn(db: DbInstance, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+
+        // The first tick is immediate, the startup already has the latest data on disk
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let (dir, keep) = {
+                let config = crate::read_config().await;
+                (config.snapshot.dir.to_owned(), config.snapshot.keep)
+            };
+
+            match take(&db, &dir, keep).await {
+                Ok(snapshot) => info!("Saved the snapshot {}", snapshot.name()),
+                Err(why) => error!("Cannot save a snapshot of the database\n{:?}", why),
+            }
+        }
+    })
+}
+
+/// Restore `snapshot` (a file name in `dir`) on the next startup
+pub async fn schedule_restore(dir: &Path, snapshot: &SnapshotFile) -> Result<()> {
+    fs::create_dir_all(dir).await?;
+    fs::write(dir.join(PENDING_RESTORE), snapshot.name()).await?;
+    Ok(())
+}
+
+/// The file name of the snapshot which will be restored on the next startup
+pub async fn pending_restore(dir: &Path) -> Option<String> {
+    fs::read_to_string(dir.join(PENDING_RESTORE))
+        .await
+        .ok()
+        .map(|v| v.trim().to_owned())
+}
+
+pub async fn cancel_restore(dir: &Path) -> Result<bool> {
+    let path = dir.join(PENDING_RESTORE);
+
+    if fs::metadata(&path).await.is_err() {
+        return Ok(false);
+    }
+
+    fs::remove_file(path).await?;
+    Ok(true)
+}
+
+/// Restore the snapshot scheduled by `schedule_restore`, if any
+/// The current data is saved to a new snapshot first, so the restore can be undone
+pub async fn restore_pending(db: &DbInstance, dir: &Path, keep: usize) -> Result<()> {
+    let name = match pending_restore(dir).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    // Removed first, a snapshot failing to restore must not fail every later startup
+    cancel_restore(dir).await?;
+
+    let path = dir.join(&name);
+    let archive: Archive = serde_json::from_slice(&fs::read(&path).await?)?;
+
+    let backup = take(db, dir, keep).await?;
+    info!("Saved the current database to {} before restoring", backup.name());
+
+    let db = db.clone();
+    let report = task::spawn_blocking(move || -> Result<_> {
+        let report = archive.import(&db, &crate::schemas(), false)?;
+        db.backend().flush()?;
+        Ok(report)
+    })
+    .await??;
+
+    info!("Restored the snapshot {}\n{}", name, report);
+    Ok(())
+}
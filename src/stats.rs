@@ -0,0 +1,162 @@
+//! The record of every command invocation, for the `stats` command
+//!
+//! Every invocation is keyed by `(unix time in ms, message id)`, so the records of a time
+//! window are a range scan, and those older than `stats.keep_days` are pruned periodically
+
+use crate::constants::COMMAND_STATS;
+use crate::Result;
+use core::time::Duration;
+use db::{Batch, DbInstance, DbTree};
+use serde::{Deserialize, Serialize};
+use serenity::model::id::GuildId;
+use std::collections::HashMap;
+use tokio::task::JoinHandle;
+
+pub type StatsKey = (u64, u64);
+pub type StatsTree = DbTree<StatsKey, Invocation>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invocation {
+    pub command: String,
+    pub guild: Option<u64>,
+    pub user: u64,
+    /// In milliseconds
+    pub duration: u64,
+    pub success: bool,
+}
+
+/// The usage of a command, or of all of them
+#[derive(Debug, Default)]
+pub struct Usage {
+    pub uses: usize,
+    pub errors: usize,
+    /// In milliseconds, sorted
+    durations: Vec<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub total: Usage,
+    /// Sorted by the number of uses, the most used first
+    pub commands: Vec<(String, Usage)>,
+}
+
+impl Usage {
+    fn add(&mut self, invocation: &Invocation) {
+        self.uses += 1;
+        self.errors += usize::from(!invocation.success);
+        self.durations.push(invocation.duration);
+    }
+
+    /// In percent
+    pub fn error_rate(&self) -> f64 {
+        if self.uses == 0 {
+            0.0
+        } else {
+            self.errors as f64 * 100.0 / self.uses as f64
+        }
+    }
+
+    /// The nearest-rank percentile of the durations, `p` in `0..=100`
+    pub fn percentile(&self, p: u8) -> u64 {
+        if self.durations.is_empty() {
+            return 0;
+        }
+
+        let rank = (f64::from(p) / 100.0 * self.durations.len() as f64).ceil() as usize;
+        self.durations[rank.saturating_sub(1).min(self.durations.len() - 1)]
+    }
+}
+
+pub fn stats_tree(db: &DbInstance) -> Result<StatsTree> {
+    Ok(db.open_tree(COMMAND_STATS)?)
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+/// Save an invocation, `id` is the id of the message which invoked the command
+pub async fn record(db: &DbInstance, id: u64, invocation: Invocation) -> Result<()> {
+    stats_tree(db)?.insert_async(&(now_ms(), id), &invocation).await
+}
+
+/// The usage of the commands since `window` ago, all of them with `None`
+/// Only in a guild with `guild`, everywhere otherwise
+/// The records are added up as they are read, they are never all loaded at once
+pub async fn summary(db: &DbInstance, window: Option<Duration>, guild: Option<GuildId>) -> Result<Summary> {
+    let since = window.map_or(0, |v| now_ms().saturating_sub(v.as_millis() as u64));
+    let tree = stats_tree(db)?;
+
+    tokio::task::spawn_blocking(move || summarize(&tree, since, guild)).await?
+}
+
+fn summarize(tree: &StatsTree, since: u64, guild: Option<GuildId>) -> Result<Summary> {
+    let mut total = Usage::default();
+    let mut commands: HashMap<String, Usage> = HashMap::new();
+
+    for (_, invocation) in tree.range((since, 0)..)? {
+        if guild.is_some() && invocation.guild != guild.map(|v| v.0) {
+            continue;
+        }
+
+        total.add(&invocation);
+        commands.entry(invocation.command.to_owned()).or_default().add(&invocation);
+    }
+
+    total.durations.sort_unstable();
+
+    let mut commands: Vec<_> = commands.into_iter().collect();
+    commands.sort_by(|a, b| b.1.uses.cmp(&a.1.uses).then_with(|| a.0.cmp(&b.0)));
+
+    for (_, usage) in commands.iter_mut() {
+        usage.durations.sort_unstable();
+    }
+
+    Ok(Summary { total, commands })
+}
+
+/// How many invocations are recorded
+pub async fn count(db: &DbInstance) -> Result<usize> {
+    let tree = stats_tree(db)?;
+    Ok(tokio::task::spawn_blocking(move || tree.len()).await?)
+}
+
+/// Remove the invocations older than `keep`, return how many were removed
+pub async fn prune(db: &DbInstance, keep: Duration) -> Result<usize> {
+    let until = now_ms().saturating_sub(keep.as_millis() as u64);
+    let tree = stats_tree(db)?;
+    let old = tree.range_async(..(until, 0)).await?;
+
+    let mut batch = Batch::new();
+
+    for (key, _) in &old {
+        batch.remove(key)?;
+    }
+
+    tree.batch_async(batch).await?;
+    Ok(old.len())
+}
+
+/// Prune the old invocations every `every`, keeping `stats.keep_days` days of them
+pub fn spawn_pruner(db: DbInstance, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+
+        loop {
+            interval.tick().await;
+
+            let days = crate::read_config().await.stats.keep_days;
+
+            if days == 0 {
+                continue;
+            }
+
+            match prune(&db, Duration::from_secs(days * 24 * 60 * 60)).await {
+                Ok(0) => {}
+                Ok(n) => info!("Pruned {} old command invocations", n),
+                Err(why) => error!("Cannot prune the command invocations\n{:?}", why),
+            }
+        }
+    })
+}
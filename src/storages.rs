@@ -3,7 +3,9 @@ use crate::types::*;
 use db::DbInstance;
 use eliza::Eliza;
 use requester::Reqwest;
+use serenity::model::id::UserId;
 use serenity::prelude::TypeMapKey;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -29,11 +31,6 @@ impl TypeMapKey for DatabaseKey {
     type Value = DbInstance;
 }
 
-pub struct ReminderNotify;
-impl TypeMapKey for ReminderNotify {
-    type Value = Arc<tokio::sync::Notify>;
-}
-
 pub struct CacheStorage;
 impl TypeMapKey for CacheStorage {
     type Value = Arc<MyCache>;
@@ -43,3 +40,9 @@ pub struct AIStore;
 impl TypeMapKey for AIStore {
     type Value = MutexData<Eliza>;
 }
+
+/// Those allowed to use the `owners_only` commands, as given to the framework on startup
+pub struct OwnersKey;
+impl TypeMapKey for OwnersKey {
+    type Value = Arc<HashSet<UserId>>;
+}
@@ -0,0 +1,281 @@
+//! The custom commands of the guilds, the tags
+//!
+//! A tag is a canned response invoked with the prefix of the guild like any command,
+//! it only runs when no built-in command has the name.
+//! Every tag is keyed by `(guild id, name)`, so the tags of a guild are a prefix scan
+
+use crate::constants::TAGS;
+use crate::error::Error;
+use crate::framework::Refusal;
+use crate::slash::Origin;
+use crate::utils::themed_embed;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use db::{DbInstance, DbTree};
+use serde::{Deserialize, Serialize};
+use serenity::client::Context;
+use serenity::http::AttachmentType;
+use serenity::model::channel::Message;
+use serenity::model::id::GuildId;
+
+pub type TagKey = (u64, String);
+pub type TagTree = DbTree<TagKey, Tag>;
+
+pub const MAX_TAGS_PER_GUILD: usize = 100;
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_CONTENT_LENGTH: usize = 2000;
+pub const MAX_ATTACHMENTS: usize = 5;
+
+/// The toggle name of the tags, they are enabled, disabled and permitted as the `tags` group
+pub const TOGGLE_NAME: &str = "tags";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    /// With the placeholders, see `render`
+    pub content: String,
+    /// Sent as the description of an embed instead of a plain message
+    pub embed: bool,
+    /// The URLs of the files sent along
+    pub attachments: Vec<String>,
+    pub author: u64,
+    pub created: DateTime<Utc>,
+    pub uses: u64,
+}
+
+/// What the placeholders of a tag are replaced with
+pub struct Placeholders<'a> {
+    pub user: &'a str,
+    pub channel: &'a str,
+    pub args: &'a str,
+}
+
+impl Tag {
+    /// The content with `{user}`, `{channel}` and `{args}` replaced
+    pub fn render(&self, placeholders: &Placeholders<'_>) -> String {
+        self.content
+            .replace("{user}", placeholders.user)
+            .replace("{channel}", placeholders.channel)
+            .replace("{args}", placeholders.args)
+    }
+}
+
+pub fn tag_tree(db: &DbInstance) -> Result<TagTree> {
+    Ok(db.open_tree(TAGS)?)
+}
+
+/// The name of a new tag, lowercase
+/// A name taken by a built-in command is refused, the tag could never run
+pub fn tag_name(name: &str) -> Result<String> {
+    let name = name.to_lowercase();
+
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        let mess = format!(
+            "The name of a tag must be 1 to {} characters long",
+            MAX_NAME_LENGTH
+        );
+        return Err(Error::user(mess).into());
+    }
+
+    if crate::framework::is_reserved(&name) {
+        let mess = format!(
+            "`{}` is the name of a command, the tag could never be used",
+            name
+        );
+        return Err(Error::user(mess).into());
+    }
+
+    Ok(name)
+}
+
+pub async fn get(db: &DbInstance, guild: GuildId, name: &str) -> Result<Option<Tag>> {
+    tag_tree(db)?
+        .get_async(&(guild.0, name.to_lowercase()))
+        .await
+}
+
+/// The tags of a guild, sorted by name
+pub async fn list(db: &DbInstance, guild: GuildId) -> Result<Vec<(String, Tag)>> {
+    let tags = tag_tree(db)?.scan_prefix_async(&guild.0).await?;
+    let mut tags: Vec<_> = tags
+        .into_iter()
+        .map(|((_, name), tag)| (name, tag))
+        .collect();
+
+    // The keys are ordered by the length of the name first
+    tags.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(tags)
+}
+
+/// Count a use of the tag, only the counter is written
+/// so an edit made while the tag was sent is kept
+async fn count_use(db: &DbInstance, guild: GuildId, name: &str) -> Result<()> {
+    let tree = tag_tree(db)?;
+    let key = (guild.0, name.to_lowercase());
+    let tags = tree.clone();
+
+    tree.untyped()
+        .transaction_async(&[tree.untyped()], move |tx| {
+            let tx_tags = tx.tree(&tags)?;
+
+            if let Some(mut tag) = tx_tags.get(&key)? {
+                tag.uses += 1;
+                tx_tags.insert(&key, &tag)?;
+            }
+
+            Ok(())
+        })
+        .await
+}
+
+/// Create or replace a tag, a new one is refused once the guild has `MAX_TAGS_PER_GUILD`
+pub async fn save(db: &DbInstance, guild: GuildId, name: &str, tag: &Tag) -> Result<()> {
+    let tree = tag_tree(db)?;
+    let key = (guild.0, name.to_owned());
+
+    if tree.get_async(&key).await?.is_none() && list(db, guild).await?.len() >= MAX_TAGS_PER_GUILD {
+        let mess = format!(
+            "This server already has {} tags, delete some first",
+            MAX_TAGS_PER_GUILD
+        );
+        return Err(Error::user(mess).into());
+    }
+
+    tree.insert_async(&key, tag).await
+}
+
+pub async fn delete(db: &DbInstance, guild: GuildId, name: &str) -> Result<()> {
+    tag_tree(db)?
+        .remove_async(&(guild.0, name.to_lowercase()))
+        .await
+}
+
+/// Check the content and the files of a tag
+pub fn validate(content: &str, attachments: &[String]) -> Result<()> {
+    if content.is_empty() && attachments.is_empty() {
+        return Err(Error::user("A tag needs a content or an attached file").into());
+    }
+
+    if content.chars().count() > MAX_CONTENT_LENGTH {
+        let mess = format!(
+            "The content of a tag cannot be longer than {} characters",
+            MAX_CONTENT_LENGTH
+        );
+        return Err(Error::user(mess).into());
+    }
+
+    if attachments.len() > MAX_ATTACHMENTS {
+        let mess = format!("A tag cannot have more than {} files", MAX_ATTACHMENTS);
+        return Err(Error::user(mess).into());
+    }
+
+    Ok(())
+}
+
+/// Run the tag named after the prefix of a message which is no command, if there is one
+/// It goes through the checks of a command: the toggles, the permission rules and the stats,
+/// all of them under `TOGGLE_NAME`
+pub async fn invoke(ctx: &Context, msg: &Message, name: &str) {
+    let guild = match msg.guild_id {
+        Some(v) => v,
+        None => return,
+    };
+
+    let db = match crate::utils::get_db(ctx).await {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let tag = match get(&db, guild, name).await {
+        Ok(Some(v)) => v,
+        Ok(None) => return,
+        Err(why) => {
+            error!("Cannot read the tag {}\n{:?}", name, why);
+            return;
+        }
+    };
+
+    let origin = Origin::from(msg);
+
+    let path = crate::framework::toggle_path(TOGGLE_NAME).unwrap_or_default();
+
+    match crate::framework::refusal(ctx, &origin, TOGGLE_NAME, &path).await {
+        None => {}
+        Some(Refusal::Disabled) => return,
+        Some(refusal) => {
+            msg.channel_id
+                .say(ctx, refusal.message(TOGGLE_NAME))
+                .await
+                .ok();
+            return;
+        }
+    }
+
+    info!("Found tag {}", name);
+
+    let start = Utc::now();
+    let result = send(ctx, msg, &db, guild, name, tag).await;
+    crate::framework::conclude(ctx, &origin, TOGGLE_NAME, start, result).await;
+}
+
+async fn send(
+    ctx: &Context,
+    msg: &Message,
+    db: &DbInstance,
+    guild: GuildId,
+    name: &str,
+    tag: Tag,
+) -> Result<()> {
+    let args = args(msg, name).await;
+    let user = format!("<@{}>", msg.author.id.0);
+    let channel = format!("<#{}>", msg.channel_id.0);
+    let mut content = tag.render(&Placeholders {
+        user: &user,
+        channel: &channel,
+        args,
+    });
+
+    // The arguments may make it longer than a message can be
+    if let Some((i, _)) = content.char_indices().nth(MAX_CONTENT_LENGTH - 1) {
+        content.truncate(i);
+        content.push('…');
+    }
+
+    let mut embed = themed_embed(msg.guild_id, |c| c.information).await;
+    embed.description(&content);
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            if tag.embed && !content.is_empty() {
+                m.set_embed(embed);
+            } else if !content.is_empty() {
+                m.content(&content);
+            }
+
+            for url in &tag.attachments {
+                m.add_file(AttachmentType::Image(url));
+            }
+
+            // The arguments are typed by anyone, they must not ping
+            m.allowed_mentions(|a| a.empty_parse())
+        })
+        .await?;
+
+    count_use(db, guild, name).await
+}
+
+/// Everything typed after the prefix and the name of the tag
+async fn args<'a>(msg: &'a Message, name: &str) -> &'a str {
+    let name = name.to_lowercase();
+
+    crate::framework::prefixes(msg)
+        .await
+        .iter()
+        .filter_map(|prefix| msg.content.strip_prefix(prefix.as_str()))
+        .find_map(|rest| {
+            let rest = rest.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+
+            (rest[..end].to_lowercase() == name).then(|| rest[end..].trim())
+        })
+        .unwrap_or("")
+}
@@ -1,28 +1,29 @@
-#![allow(unstable_name_collisions)]
-
+use crate::slash::Origin;
 use crate::traits::Embedable;
 use crate::Result;
 use chrono::{DateTime, Utc};
 use core::ops::{Deref, DerefMut};
 use core::time::Duration;
+use db::DbTree;
 use serde::{Deserialize, Serialize};
 use serenity::builder::CreateEmbed;
 use serenity::client::Context;
 use serenity::http::client::Http;
-use serenity::model::channel::Message;
 use serenity::model::guild::Role;
-use serenity::model::id::{ChannelId, GuildId, RoleId, UserId};
+use serenity::model::id::{GuildId, MessageId, RoleId, UserId};
 use smallstr::SmallString;
 use std::borrow::Borrow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::default::Default;
 use std::fmt;
-use std::sync::atomic::{AtomicUsize, Ordering};
 
-use magic::traits::MagicBool as _;
 use magic::traits::MagicIter as _;
 use magic::traits::MagicStr as _;
 
+pub type GuildConfigTree = DbTree<u64, GuildConfig>;
+pub type ReminderTree = DbTree<i64, Reminder>;
+pub type GenshinWatchTree = DbTree<u64, u64>;
+
 pub(crate) struct Ref<T>(pub T);
 
 impl<T> From<T> for Ref<T> {
@@ -54,7 +55,6 @@ impl<T> DerefMut for Ref<T> {
 pub struct Information {
     pub booted_on: DateTime<Utc>,
     pub user_id: UserId,
-    pub executed: AtomicUsize,
 }
 
 impl Information {
@@ -62,22 +62,11 @@ impl Information {
         let info = Self {
             booted_on: Utc::now(),
             user_id: http.get_current_user().await?.id,
-            executed: AtomicUsize::new(0),
         };
 
         Ok(info)
     }
 
-    #[inline]
-    pub fn executed_commands(&self) -> usize {
-        self.executed.load(Ordering::SeqCst)
-    }
-
-    #[inline]
-    pub fn executed_one(&self) -> usize {
-        self.executed.fetch_add(1, Ordering::SeqCst)
-    }
-
     pub fn uptime(&self) -> Duration {
         let current = Utc::now().timestamp_millis() as u64;
         let since = self.booted_on.timestamp_millis() as u64;
@@ -98,14 +87,21 @@ pub struct Reminder {
 }
 
 impl Reminder {
-    pub fn new(msg: &Message, duration: Duration, content: &str) -> Self {
+    /// `msg_id` is the message the reminder links back to
+    pub fn new(
+        origin: &Origin<'_>,
+        msg_id: MessageId,
+        when: DateTime<Utc>,
+        duration: Duration,
+        content: &str,
+    ) -> Self {
         Self {
-            user_id: msg.author.id.0,
-            msg_id: msg.id.0,
-            channel_id: msg.channel_id.0,
-            guild_id: msg.guild_id.map(|v| v.0),
+            user_id: origin.author().id.0,
+            msg_id: msg_id.0,
+            channel_id: origin.channel_id().0,
+            guild_id: origin.guild_id().map(|v| v.0),
             content: content.to_option().map(String::from),
-            when: Utc::now(),
+            when,
             duration,
         }
     }
@@ -153,7 +149,7 @@ impl Embedable for Reminder {
     }
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimpleRole {
     pub name: SmallString<[u8; 32]>,
     pub id: u64,
@@ -176,14 +172,14 @@ impl fmt::Display for SimpleRole {
     }
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct DiscordLogger {
     pub enable: bool,
     // pub channel: Option<ChannelId>,
     pub channel: Option<u64>,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct FindSauce {
     pub all: bool,
     pub enable: bool,
@@ -210,7 +206,7 @@ impl Embedable for FindSauce {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FindSadKaede {
     pub all: bool,
     pub enable: bool,
@@ -249,7 +245,7 @@ impl Embedable for FindSadKaede {
     }
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct RepeatWords {
     pub enable: bool,
     pub words: HashSet<String>,
@@ -264,7 +260,7 @@ impl Embedable for RepeatWords {
         } else if self.words.is_empty() {
             embed.description(
                 "Error 404: Word not found
-            Use `option repeat_words add` command to add words to be repeated",
+            Use `option add repeat_words.words` command to add words to be repeated",
             );
         } else {
             let words = self.words.iter().map(|v| format!("`{}`", v)).join(", ");
@@ -277,7 +273,124 @@ impl Embedable for RepeatWords {
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+/// Commands, command groups and auto-commands explicitly turned on or off,
+/// by their lowercase name
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Toggles {
+    pub enabled: HashSet<String>,
+    pub disabled: HashSet<String>,
+}
+
+impl Toggles {
+    /// `None` when the name has not been toggled
+    pub fn get(&self, name: &str) -> Option<bool> {
+        let name = name.to_lowercase();
+
+        if self.enabled.contains(&name) {
+            Some(true)
+        } else if self.disabled.contains(&name) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, name: &str, enable: bool) {
+        let name = name.to_lowercase();
+
+        if enable {
+            self.disabled.remove(&name);
+            self.enabled.insert(name);
+        } else {
+            self.enabled.remove(&name);
+            self.disabled.insert(name);
+        }
+    }
+
+    /// Go back to the default, return whether the name was toggled
+    pub fn unset(&mut self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.enabled.remove(&name) | self.disabled.remove(&name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.enabled.is_empty() && self.disabled.is_empty()
+    }
+}
+
+/// Who a permission rule applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PermissionTarget {
+    User(u64),
+    Role(u64),
+    Channel(u64),
+    Everyone,
+}
+
+impl fmt::Display for PermissionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(id) => write!(f, "<@{}>", id),
+            Self::Role(id) => write!(f, "<@&{}>", id),
+            Self::Channel(id) => write!(f, "<#{}>", id),
+            Self::Everyone => f.write_str("everyone"),
+        }
+    }
+}
+
+/// Allow or deny a command or a command group, by its lowercase name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionRule {
+    pub name: String,
+    pub target: PermissionTarget,
+    pub allow: bool,
+}
+
+/// Who is asking, to find the permission rules which apply
+#[derive(Debug, Clone, Copy)]
+pub struct Invoker<'a> {
+    pub user: u64,
+    pub roles: &'a [u64],
+    pub channel: u64,
+}
+
+impl PermissionRule {
+    fn applies_to(&self, invoker: &Invoker<'_>) -> bool {
+        match self.target {
+            PermissionTarget::User(id) => id == invoker.user,
+            PermissionTarget::Role(id) => invoker.roles.contains(&id),
+            PermissionTarget::Channel(id) => id == invoker.channel,
+            PermissionTarget::Everyone => true,
+        }
+    }
+
+    /// How specific the target is, the lowest wins
+    fn level(&self) -> u8 {
+        match self.target {
+            PermissionTarget::User(_) => 0,
+            PermissionTarget::Role(_) => 1,
+            PermissionTarget::Channel(_) => 2,
+            PermissionTarget::Everyone => 3,
+        }
+    }
+}
+
+/// The look of the embeds in a guild, every unset value falls back to the global config
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildTheme {
+    pub information: Option<u64>,
+    pub success: Option<u64>,
+    pub error: Option<u64>,
+    pub message_update: Option<u64>,
+    pub message_delete: Option<u64>,
+    pub lovely: Option<u64>,
+    /// Shown on the embeds without a thumbnail of their own
+    pub thumbnail: Option<String>,
+    /// Shown on the embeds without a footer of their own
+    pub footer: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 //#[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct GuildConfig {
@@ -289,6 +402,10 @@ pub struct GuildConfig {
     pub find_sauce: FindSauce,
     pub find_sadkaede: FindSadKaede,
     pub repeat_words: RepeatWords,
+    pub toggles: Toggles,
+    pub channel_toggles: HashMap<u64, Toggles>,
+    pub theme: GuildTheme,
+    pub permissions: Vec<PermissionRule>,
 }
 
 impl GuildConfig {
@@ -311,72 +428,94 @@ impl GuildConfig {
             && self.rgblized.is_none()
             && self.repeat_words.words.is_empty()
             && !self.repeat_words.enable
-    }
-
-    pub fn set_prefix<S: ToString>(&mut self, prefix: S) -> Option<SmallString<[u8; 8]>> {
-        let old = self.prefix.clone();
-        self.prefix = Some(SmallString::from(prefix.to_string()));
-        old
-    }
-
-    pub fn remove_prefix(&mut self) -> Option<SmallString<[u8; 8]>> {
-        let old = self.prefix.clone();
-        self.prefix = None;
-        old
-    }
-
-    pub fn set_log_channel<C: Into<ChannelId>>(&mut self, channel: C) -> Option<ChannelId> {
-        let old = self.logger.channel;
-        self.logger.channel = Some(channel.into().0);
-        old.map(ChannelId)
-    }
+            && self.toggles.is_empty()
+            && self.channel_toggles.is_empty()
+            && self.theme == GuildTheme::default()
+            && self.permissions.is_empty()
+    }
+
+    /// Whether something may run in the channel, `names` being a command followed by its groups
+    /// or an auto-command
+    /// The channel toggles win over the guild ones, which win over `default`
+    /// and the first toggled name wins at each level, so a command can be enabled in a disabled group
+    pub fn is_enabled(&self, channel: u64, names: &[&str], default: bool) -> bool {
+        let levels = self.channel_toggles.get(&channel).into_iter().chain(Some(&self.toggles));
+
+        for toggles in levels {
+            if let Some(v) = names.iter().find_map(|name| toggles.get(name)) {
+                return v;
+            }
+        }
 
-    pub fn enable_logger(&mut self) {
-        self.logger.enable = true;
+        default
     }
 
-    pub fn disable_logger(&mut self) {
-        self.logger.enable = false;
+    /// Get the toggles of a channel, or of the guild with `None`
+    pub fn toggles_mut(&mut self, channel: Option<u64>) -> &mut Toggles {
+        match channel {
+            Some(c) => self.channel_toggles.entry(c).or_default(),
+            None => &mut self.toggles,
+        }
     }
 
-    /// Toggle the logger on/off, return the new state
-    pub fn toggle_logger(&mut self) -> bool {
-        let status = !self.logger.enable;
-        self.logger.enable = status;
-        status
+    /// Drop the channels without any toggle left
+    pub fn prune_toggles(&mut self) {
+        self.channel_toggles.retain(|_, v| !v.is_empty());
     }
+    
+    /// The rule deciding whether the invoker may use something, `names` being a command
+    /// followed by its groups, `None` when no rule applies
+    /// The first name with a rule wins, so a command can be allowed in a denied group,
+    /// then the most specific target: a user, a role, a channel and everyone.
+    /// Between the roles of the invoker an allow wins over a deny
+    pub fn permission(&self, names: &[&str], invoker: &Invoker<'_>) -> Option<&PermissionRule> {
+        names.iter().find_map(|name| {
+            let name = name.to_lowercase();
 
-    pub fn enable_find_sauce(&mut self) {
-        self.find_sauce.enable = true;
+            self.permissions
+                .iter()
+                .filter(|v| v.name == name && v.applies_to(invoker))
+                .min_by_key(|v| (v.level(), !v.allow))
+        })
     }
 
-    pub fn disable_find_sauce(&mut self) {
-        self.find_sauce.enable = false;
+    /// Add a rule, replacing the one for the same name and target
+    pub fn set_permission(&mut self, name: &str, target: PermissionTarget, allow: bool) {
+        let name = name.to_lowercase();
+        self.unset_permission(&name, target);
+        self.permissions.push(PermissionRule { name, target, allow });
     }
 
-    pub fn add_sauce_channel<C: Into<ChannelId>>(&mut self, channel: C) {
-        let channel = channel.into();
-        self.find_sauce.channels.insert(channel.0);
+    /// Remove the rule for the name and target, return whether there was one
+    pub fn unset_permission(&mut self, name: &str, target: PermissionTarget) -> bool {
+        let name = name.to_lowercase();
+        let len = self.permissions.len();
+        self.permissions.retain(|v| v.name != name || v.target != target);
+        self.permissions.len() != len
     }
 
-    pub fn remove_sauce_channel<C: Into<ChannelId>>(&mut self, channel: C) -> Option<ChannelId> {
-        let channel = channel.into();
-        self.find_sauce
-            .channels
-            .remove(&channel.0)
-            .then_some(channel)
-    }
-    
     /// Add roles to RGB, return the count of added roles
     pub fn add_rgb<I>(&mut self, roles: I) -> u8
     where
         I: IntoIterator<Item = Role>,
+    {
+        let roles = roles
+            .into_iter()
+            .filter(|v| v.mentionable)
+            .map(SimpleRole::from);
+
+        self.extend_rgb(roles)
+    }
+
+    /// Same as `add_rgb`, the roles are expected to be mentionable
+    pub fn extend_rgb<I>(&mut self, roles: I) -> u8
+    where
+        I: IntoIterator<Item = SimpleRole>,
     {
         let rgb = self.rgblized.get_or_insert_with(Vec::new);
         let roles = roles
             .into_iter()
-            .filter(|v| v.mentionable && rgb.iter().all(|x| v.id != x.id))
-            .map(SimpleRole::from)
+            .filter(|v| rgb.iter().all(|x| v.id != x.id))
             .collect::<Vec<_>>();
         let length = roles.len() as u8;
         rgb.extend(roles);
@@ -421,65 +560,6 @@ impl GuildConfig {
 
         length
     }
-
-    pub fn add_words<I, S>(&mut self, words: I) -> u8
-    where
-        I: IntoIterator<Item = S>,
-        S: ToString,
-    {
-        let mut added = 0u8;
-
-        for word in words {
-            let w = word.to_string();
-
-            if w.is_empty() {
-                continue;
-            }
-
-            if self.repeat_words.words.insert(w) {
-                added += 1;
-            }
-        }
-
-        added
-    }
-
-    pub fn remove_words<I, S>(&mut self, words: I) -> u8
-    where
-        I: IntoIterator<Item = S>,
-        S: ToString,
-    {
-        if self.repeat_words.words.is_empty() {
-            return 0;
-        }
-
-        let mut removed = 0u8;
-
-        for word in words {
-            let w = word.to_string();
-            if self.repeat_words.words.remove(&w) {
-                removed += 1;
-            }
-        }
-
-        removed
-    }
-
-    #[inline]
-    pub fn enable_repeat_words(&mut self) {
-        self.repeat_words.enable = true;
-    }
-
-    #[inline]
-    pub fn disable_repeat_words(&mut self) {
-        self.repeat_words.enable = false;
-    }
-
-    /// Toggle the repeat words, return the current state.
-    pub fn toggle_repeat_words(&mut self) -> bool {
-        self.repeat_words.enable = !self.repeat_words.enable;
-        self.repeat_words.enable
-    }
 }
 
 // pub enum PlayingSignal {
@@ -14,13 +14,18 @@ use colorful::Colorful;
 use colorful::RGB;
 
 use crate::{
+    constants::{GUILD_CONFIG, REMINDERS, REMINDERS_BY_USER},
+    error::Error,
     storages::*,
     traits::{Embedable, Paginator, PaginatorOption},
-    types::GuildConfig,
+    types::{GuildConfig, GuildConfigTree, Reminder, ReminderTree},
     Result,
 };
 
+use db::{DbInstance, DbTree};
+
 use serenity::{
+    builder::CreateEmbed,
     client::Context,
     model::{
         channel::{Message, ReactionType},
@@ -171,23 +176,92 @@ where
     ctx.data.read().await.get::<D>().cloned()
 }
 
-// #[rustfmt_skip]
-pub async fn update_guild_config(ctx: &Context, new_config: &GuildConfig) -> Result<()> {
-    let key = new_config.id;
-    let config_db = get_data::<DatabaseKey>(ctx)
+/// The database stored in the context
+pub async fn get_db(ctx: &Context) -> Result<DbInstance> {
+    let db = get_data::<DatabaseKey>(ctx)
         .await
-        .unwrap()
-        .open("GuildConfig")?;
-
-    tokio::task::block_in_place(move || {
-        if new_config.is_default() {
-            config_db.remove(&key)
-        } else {
-            config_db.insert(&key, new_config)
-        }
-    })?;
+        .ok_or_else(|| Error::internal("The database is not in the context"))?;
 
-    Ok(())
+    Ok(db)
+}
+
+/// Open a typed tree of the database stored in the context
+pub async fn open_tree<K, V>(ctx: &Context, name: &str) -> Result<DbTree<K, V>> {
+    Ok(get_db(ctx).await?.open_tree(name)?)
+}
+
+/// The reminders, indexed by their owner
+pub fn reminder_tree(db: &DbInstance) -> Result<ReminderTree> {
+    db.open_tree::<i64, Reminder, _>(REMINDERS)?
+        .with_index(REMINDERS_BY_USER, |_, v| vec![v.user_id])
+}
+
+pub async fn open_reminders(ctx: &Context) -> Result<ReminderTree> {
+    let db = get_db(ctx).await?;
+
+    // Building the index the first time reads the whole tree
+    tokio::task::spawn_blocking(move || reminder_tree(&db)).await?
+}
+
+/// The config of a guild as stored in the database, a new one if there is none
+pub async fn stored_guild_config(ctx: &Context, guild_id: GuildId) -> Result<GuildConfig> {
+    let config_db: GuildConfigTree = open_tree(ctx, GUILD_CONFIG).await?;
+    let config = config_db.get_async(&guild_id.0).await?;
+
+    Ok(config.unwrap_or_else(|| GuildConfig::new(guild_id)))
+}
+
+/// Change the stored config of a guild in a single transaction,
+/// so two commands changing it at the same time never overwrite each other
+/// `f` is given the stored config (or a new one) and may be run again on a conflict,
+/// a config back to its default is removed
+/// The config in memory follows the database through its watcher, it is never written here
+pub async fn update_guild_config<F, T>(ctx: &Context, guild_id: GuildId, f: F) -> Result<T>
+where
+    F: FnMut(&mut GuildConfig) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    update_stored_guild_config(&get_db(ctx).await?, guild_id, f).await
+}
+
+/// `update_guild_config` on the given database
+pub async fn update_stored_guild_config<F, T>(
+    db: &DbInstance,
+    guild_id: GuildId,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut(&mut GuildConfig) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let config_db: GuildConfigTree = db.open_tree(GUILD_CONFIG)?;
+    let tree = config_db.clone();
+    let key = guild_id.0;
+
+    config_db
+        .untyped()
+        .transaction_async(&[config_db.untyped()], move |tx| {
+            let guilds = tx.tree(&tree)?;
+            let mut config = guilds.get(&key)?.unwrap_or_else(|| GuildConfig::new(key));
+            let res = f(&mut config);
+
+            if config.is_default() {
+                guilds.remove(&key)?;
+            } else {
+                guilds.insert(&key, &config)?;
+            }
+
+            Ok(res)
+        })
+        .await
+}
+
+/// A new embed in the theme of the guild, see `Theme::embed`
+pub async fn themed_embed(
+    guild_id: Option<GuildId>,
+    color: fn(&crate::config::Color) -> u64,
+) -> CreateEmbed {
+    crate::read_config().await.theme(guild_id).embed(color)
 }
 
 pub async fn get_file_bytes(url: impl AsRef<str>) -> Result<Bytes> {
@@ -295,3 +369,104 @@ pub async fn react_to_embed_then_pagination<P: Paginator + Embedable + Send + Sy
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn reminder(user_id: u64, msg_id: u64) -> Reminder {
+        Reminder {
+            user_id,
+            msg_id,
+            channel_id: 1,
+            guild_id: None,
+            content: None,
+            when: Utc::now(),
+            duration: Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn store_the_guild_config() {
+        let db = DbInstance::memory();
+        let guild = GuildId(1);
+
+        let old =
+            update_stored_guild_config(&db, guild, |config| config.prefix.replace("~".into()))
+                .await
+                .unwrap();
+
+        assert!(old.is_none());
+
+        let tree: GuildConfigTree = db.open_tree(GUILD_CONFIG).unwrap();
+        let stored = tree.get(&guild.0).unwrap().unwrap();
+        assert_eq!(stored.id, guild.0);
+        assert_eq!(stored.prefix.as_deref(), Some("~"));
+    }
+
+    #[tokio::test]
+    async fn remove_the_default_guild_config() {
+        let db = DbInstance::memory();
+        let guild = GuildId(1);
+
+        update_stored_guild_config(&db, guild, |config| config.prefix = Some("~".into()))
+            .await
+            .unwrap();
+        update_stored_guild_config(&db, guild, |config| config.prefix = None)
+            .await
+            .unwrap();
+
+        let tree: GuildConfigTree = db.open_tree(GUILD_CONFIG).unwrap();
+        assert!(tree.get(&guild.0).unwrap().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn keep_every_concurrent_guild_config_update() {
+        let db = DbInstance::memory();
+        let guild = GuildId(1);
+
+        let updates = (0..20u64).map(|channel| {
+            let db = db.clone();
+
+            tokio::spawn(async move {
+                update_stored_guild_config(&db, guild, move |config| {
+                    config.find_sauce.channels.insert(channel);
+                })
+                .await
+            })
+        });
+
+        for update in future::join_all(updates).await {
+            update.unwrap().unwrap();
+        }
+
+        let tree: GuildConfigTree = db.open_tree(GUILD_CONFIG).unwrap();
+        let stored = tree.get(&guild.0).unwrap().unwrap();
+        assert_eq!(stored.find_sauce.channels.len(), 20);
+    }
+
+    #[test]
+    fn index_the_reminders_by_user() {
+        let db = DbInstance::memory();
+        let reminders = reminder_tree(&db).unwrap();
+
+        reminders.insert(&1, &reminder(10, 100)).unwrap();
+        reminders.insert(&2, &reminder(20, 200)).unwrap();
+        reminders.insert(&3, &reminder(10, 300)).unwrap();
+
+        let mine = reminders.get_by_index(REMINDERS_BY_USER, &10u64).unwrap();
+        let ids: Vec<_> = mine.iter().map(|(k, v)| (*k, v.msg_id)).collect();
+        assert_eq!(ids, vec![(1, 100), (3, 300)]);
+
+        reminders.remove(&1).unwrap();
+
+        // A tree opened again builds the index from what is stored
+        let reopened = reminder_tree(&db).unwrap();
+        let mine = reopened.get_by_index(REMINDERS_BY_USER, &10u64).unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].0, 3);
+        let theirs = reopened.get_by_index(REMINDERS_BY_USER, &30u64).unwrap();
+        assert!(theirs.is_empty());
+    }
+}